@@ -1,21 +1,107 @@
-use crate::utils::financial::cagr;
-use clap::Parser;
+use crate::core::tax::TaxRates;
+use crate::utils::financial::{cagr, real_rate};
+use clap::{Args, Parser};
 
 use super::port::Run;
 
+/// Capital gains tax rates of a taxable account, shared by the commands computing after-tax returns
+#[derive(Args, Debug)]
+pub struct TaxArgs {
+    #[arg(long, help = "Optional long term capital gains tax rate, ex: 0.15")]
+    capital_gains_tax: Option<f32>,
+    #[arg(
+        long,
+        requires = "capital_gains_tax",
+        help = "Optional tax rate of the gains held less than --long-term-days, ex: 0.35"
+    )]
+    short_term_tax: Option<f32>,
+    #[arg(
+        long,
+        default_value_t = 365,
+        help = "Holding period in days from which the long term capital gains rate applies"
+    )]
+    long_term_days: i64,
+}
+
+impl TaxArgs {
+    /// Tax rates given on the command line, none when no rate is given
+    pub fn rates(&self) -> Option<TaxRates> {
+        self.capital_gains_tax.map(|capital_gains| {
+            TaxRates::new()
+                .add_capital_gains(capital_gains)
+                .add_short_term(self.short_term_tax)
+                .add_long_term_days(self.long_term_days)
+        })
+    }
+}
+
 #[derive(Parser, Debug)]
 #[clap(about = "Calculated the compounded anual growth rate")]
 pub struct CagrCliImpl {
     final_value: f32,
     initial_value: f32,
     periods: i32,
+    #[arg(
+        long,
+        help = "Optional average annual inflation over the periods, ex: 0.03, adds the real CAGR"
+    )]
+    inflation: Option<f32>,
+    #[arg(
+        long,
+        help = "Optional dividend yield reinvested every year, ex: 0.02, adds the total CAGR"
+    )]
+    dividend_yield: Option<f32>,
+    #[command(flatten)]
+    tax: TaxArgs,
+    #[arg(long, help = "Optional tax rate of the dividends, ex: 0.15")]
+    dividend_tax: Option<f32>,
 }
 
 impl Run for CagrCliImpl {
     fn run(self) {
-        println!(
-            "{}",
-            cagr(self.final_value, self.initial_value, self.periods)
-        )
+        let nominal = cagr(self.final_value, self.initial_value, self.periods);
+        let tax = match (self.tax.rates(), self.dividend_tax) {
+            (None, None) => None,
+            (rates, dividend_tax) => Some(
+                rates
+                    .unwrap_or_else(TaxRates::new)
+                    .add_dividend(dividend_tax.unwrap_or(0.0)),
+            ),
+        };
+
+        if self.inflation.is_none() && self.dividend_yield.is_none() && tax.is_none() {
+            println!("{}", nominal);
+            return;
+        }
+
+        let dividend_yield = self.dividend_yield.unwrap_or(0.0);
+        // the compounding of the dividends without any tax
+        let (total_value, _) =
+            TaxRates::new().compound(self.initial_value, nominal, dividend_yield, self.periods);
+        let mut result = cagr(total_value, self.initial_value, self.periods);
+
+        if self.dividend_yield.is_some() {
+            println!("Price CAGR: {:.2}%", nominal * 100.0);
+            println!("Total CAGR: {:.2}%", result * 100.0);
+        } else {
+            println!("Nominal CAGR: {:.2}%", nominal * 100.0);
+        }
+
+        if let Some(tax) = tax {
+            let after_tax = tax.after_tax_cagr(
+                self.final_value,
+                self.initial_value,
+                self.periods,
+                dividend_yield,
+            );
+            println!("After-Tax CAGR: {:.2}%", after_tax * 100.0);
+            println!("Tax Drag: {:.2}%", (result - after_tax) * 100.0);
+            result = after_tax;
+        }
+
+        if let Some(inflation) = self.inflation {
+            println!("Inflation: {:.2}%", inflation * 100.0);
+            println!("Real CAGR: {:.2}%", real_rate(result, inflation) * 100.0);
+        }
     }
 }
@@ -0,0 +1,41 @@
+use super::port::Run;
+use crate::Dhandho;
+use clap::{CommandFactory, Parser};
+use clap_complete::{generate, Shell};
+use std::io;
+
+#[derive(Parser, Debug)]
+#[command(about = "Prints the completion script of a shell. Ex: dhandho completions zsh")]
+pub struct CompletionsCliImpl {
+    #[arg(help = "Shell to complete: bash, zsh, fish, elvish or powershell")]
+    shell: Shell,
+}
+
+impl Run for CompletionsCliImpl {
+    fn run(self) {
+        let mut command = Dhandho::command();
+        let name = command.get_name().to_string();
+
+        generate(self.shell, &mut command, name, &mut io::stdout());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_definition_is_valid() {
+        Dhandho::command().debug_assert();
+    }
+
+    #[test]
+    fn test_bash_completions_are_generated() {
+        let mut command = Dhandho::command();
+        let mut script = Vec::new();
+
+        generate(Shell::Bash, &mut command, "dhandho", &mut script);
+
+        assert!(!script.is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+use super::port::{exit_with_error, Run};
+use crate::core::comps_builder::{CompsBuilder, Peer};
+use clap::Parser;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Parser, Debug)]
+#[command(about = "Values a company with the multiples of its peers")]
+pub struct CompsCliImpl {
+    #[arg(
+        long,
+        required = true,
+        help = "CSV or TOML file with ticker,price,eps,ebitda,fcf,book_value,net_debt per share"
+    )]
+    peers: String,
+    #[arg(short, long, help = "Optional earnings per share of the target")]
+    eps: Option<f32>,
+    #[arg(long, help = "Optional EBITDA per share of the target")]
+    ebitda: Option<f32>,
+    #[arg(short, long, help = "Optional free cash flow per share of the target")]
+    fcf: Option<f32>,
+    #[arg(short, long, help = "Optional book value per share of the target")]
+    book_value: Option<f32>,
+    #[arg(
+        short,
+        long,
+        allow_hyphen_values = true,
+        help = "Optional net debt per share of the target"
+    )]
+    net_debt: Option<f32>,
+    #[arg(long, help = "Optional DCF value per share to compare with")]
+    dcf_value: Option<f32>,
+    #[arg(short, long, help = "Optional share price for the margin of safety")]
+    price: Option<f32>,
+}
+
+#[derive(Deserialize)]
+struct PeersFile {
+    peer: Vec<Peer>,
+}
+
+/// Reads peers from a TOML file with [[peer]] tables or from a CSV file with headers,
+/// the invalid CSV rows are reported and skipped
+pub fn read_peers(path: &str) -> Vec<Peer> {
+    let content = fs::read_to_string(path).unwrap_or_else(|error| {
+        exit_with_error(format!("cannot read the peers file {}: {}", path, error))
+    });
+
+    if Path::new(path).extension().is_some_and(|ext| ext == "toml") {
+        let file: PeersFile = toml::from_str(&content).unwrap_or_else(|error| {
+            exit_with_error(format!("invalid peers file {}: {}", path, error))
+        });
+
+        return file.peer;
+    }
+
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let headers = reader
+        .headers()
+        .cloned()
+        .unwrap_or_else(|error| exit_with_error(format!("invalid peers file {}: {}", path, error)));
+
+    reader
+        .records()
+        .filter_map(|record| {
+            let peer = record
+                .map_err(|error| error.to_string())
+                .and_then(|record| {
+                    record.deserialize::<Peer>(Some(&headers)).map_err(|error| {
+                        format!("{}: {}", record.get(0).unwrap_or_default(), error)
+                    })
+                });
+
+            peer.inspect_err(|error| eprintln!("skipped {}", error))
+                .ok()
+        })
+        .collect()
+}
+
+impl Run for CompsCliImpl {
+    fn run(self) {
+        CompsBuilder::new()
+            .add_peers(read_peers(&self.peers))
+            .add_eps(self.eps)
+            .add_ebitda(self.ebitda)
+            .add_fcf(self.fcf)
+            .add_book_value(self.book_value)
+            .add_net_debt(self.net_debt)
+            .add_dcf_value(self.dcf_value)
+            .add_share_price(self.price)
+            .execute();
+    }
+}
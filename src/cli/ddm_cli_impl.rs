@@ -0,0 +1,59 @@
+use super::intrinsic_cli_impl::growth_assumptions;
+use super::port::{exit_with_error, load_config, Run};
+use crate::core::ddm_builder::DdmBuilder;
+use crate::core::growth_assumption_builder::GrowthAssumption;
+use crate::utils::grammar::parse_rate;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(about = "Values a share by discounting its future dividends")]
+pub struct DdmCliImpl {
+    #[arg(
+        short = 'D',
+        long,
+        required = true,
+        help = "Dividend per share paid over the last year"
+    )]
+    dividend: f32,
+    #[arg(
+        short,
+        long,
+        value_parser = parse_rate,
+        help = "Cost of equity, defaults to the discount rate of the config file or 0.15"
+    )]
+    rate: Option<f32>,
+    #[arg(
+        short,
+        long,
+        help = "Optional dividend growth stages in the format \"5y @ 12% -1%/y\" or years,rate,growth_incr"
+    )]
+    growth_assumptions: Vec<GrowthAssumption>,
+    #[arg(
+        short,
+        long,
+        required_unless_present = "growth_assumptions",
+        help = "Perpetual dividend growth, defaults to the last growth stage"
+    )]
+    terminal_growth: Option<f32>,
+    #[arg(long, help = "Optional share price for the margin of safety")]
+    price: Option<f32>,
+}
+
+impl Run for DdmCliImpl {
+    fn run(self) {
+        let rate = self.rate.unwrap_or_else(|| load_config().discount_rate());
+
+        let ddm = DdmBuilder::new()
+            .add_dividend(self.dividend)
+            .add_rate(rate)
+            .add_growth_assumptions(growth_assumptions(self.growth_assumptions))
+            .add_terminal_growth(self.terminal_growth)
+            .add_share_price(self.price);
+
+        if let Err(error) = ddm.validate() {
+            exit_with_error(error);
+        }
+
+        ddm.execute();
+    }
+}
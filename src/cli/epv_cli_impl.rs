@@ -0,0 +1,87 @@
+use super::intrinsic_cli_impl::growth_assumptions;
+use super::port::{load_config, Run};
+use crate::core::epv_builder::EpvBuilder;
+use crate::core::growth_assumption_builder::GrowthAssumption;
+use crate::core::intrinsic_builder::IntrinsicBuilder;
+use crate::utils::grammar::parse_rate;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(about = "Computes the earnings power value and compares it to assets and growth")]
+pub struct EpvCliImpl {
+    #[arg(
+        short,
+        long,
+        required = true,
+        help = "Normalized operating earnings (EBIT)"
+    )]
+    ebit: f32,
+    #[arg(short, long, default_value = "0.0", help = "Tax rate")]
+    tax_rate: f32,
+    #[arg(
+        short,
+        long,
+        value_parser = parse_rate,
+        help = "Cost of capital, defaults to the discount rate of the config file or 0.15"
+    )]
+    rate: Option<f32>,
+    #[arg(
+        long,
+        requires = "maintenance_capex",
+        help = "Optional depreciation and amortization, given with the maintenance capex"
+    )]
+    depreciation: Option<f32>,
+    #[arg(
+        long,
+        requires = "depreciation",
+        help = "Optional capex needed to sustain the current earnings"
+    )]
+    maintenance_capex: Option<f32>,
+    #[arg(short, long, help = "Optional cash amount")]
+    cash: Option<f32>,
+    #[arg(short, long, help = "Optional debt amount")]
+    debt: Option<f32>,
+    #[arg(short, long, help = "Optional shares outstanding")]
+    shares_outstanding: Option<f32>,
+    #[arg(long, help = "Optional cost of reproducing the assets of the business")]
+    reproduction_value: Option<f32>,
+    #[arg(
+        short,
+        long,
+        help = "Optional growth assumptions in the format \"5y @ 12% -1%/y\" or years,rate,growth_incr to compare with a DCF"
+    )]
+    growth_assumptions: Vec<GrowthAssumption>,
+}
+
+impl Run for EpvCliImpl {
+    fn run(self) {
+        let rate = self.rate.unwrap_or_else(|| load_config().discount_rate());
+        let epv = EpvBuilder::new()
+            .add_ebit(self.ebit)
+            .add_tax_rate(self.tax_rate)
+            .add_rate(rate)
+            .add_depreciation(self.depreciation)
+            .add_maintenance_capex(self.maintenance_capex)
+            .add_cash(self.cash)
+            .add_debt(self.debt)
+            .add_shares_outstanding(self.shares_outstanding)
+            .add_reproduction_value(self.reproduction_value);
+
+        // the DCF grows the same adjusted earnings, so the gap is the value of growth
+        let growth_value = if self.growth_assumptions.is_empty() {
+            None
+        } else {
+            let valuation = IntrinsicBuilder::new()
+                .add_current_value(epv.adjusted_earnings())
+                .add_growth_assumptions(growth_assumptions(self.growth_assumptions))
+                .add_rate(rate)
+                .add_cash(self.cash)
+                .add_debt(self.debt)
+                .valuation();
+
+            Some(valuation.intrinsic)
+        };
+
+        epv.add_growth_value(growth_value).execute();
+    }
+}
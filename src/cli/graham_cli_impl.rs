@@ -0,0 +1,92 @@
+use crate::utils::financial::{graham_number, graham_value, margin_of_safety};
+use clap::Parser;
+
+use super::port::{exit_with_error, Run};
+
+#[derive(Parser, Debug)]
+#[command(about = "Graham number and Graham formula value. Ex: -e 5.2 -b 38.0 -g 0.07")]
+pub struct GrahamCliImpl {
+    #[arg(
+        short,
+        long,
+        required = true,
+        allow_hyphen_values = true,
+        help = "Earnings per share"
+    )]
+    eps: f32,
+    #[arg(
+        short,
+        long,
+        help = "Optional book value per share for the Graham number"
+    )]
+    book_value: Option<f32>,
+    #[arg(
+        short,
+        long,
+        allow_hyphen_values = true,
+        help = "Optional expected annual growth for the next 7-10 years, ex: 0.07"
+    )]
+    growth: Option<f32>,
+    #[arg(
+        short = 'y',
+        long,
+        default_value = "0.044",
+        help = "Current yield of AAA corporate bonds"
+    )]
+    bond_yield: f32,
+    #[arg(short, long, help = "Optional share price for the margin of safety")]
+    price: Option<f32>,
+}
+
+impl Run for GrahamCliImpl {
+    fn run(self) {
+        let mut values = vec![];
+
+        if let Some(book_value) = self.book_value {
+            let number = graham_number(self.eps, book_value).unwrap_or_else(|| {
+                exit_with_error(format!(
+                    "the Graham number needs a positive EPS and book value, got {} and {}",
+                    self.eps, book_value
+                ))
+            });
+            values.push(("Graham Number", number));
+        }
+
+        if let Some(growth) = self.growth {
+            if self.bond_yield <= 0.0 {
+                exit_with_error(format!(
+                    "the Graham value needs a positive bond yield, got {}",
+                    self.bond_yield
+                ));
+            }
+
+            let value = graham_value(self.eps, growth, self.bond_yield).unwrap_or_else(|| {
+                exit_with_error(format!(
+                    "the Graham value of an EPS of {} growing {} is not positive",
+                    self.eps, growth
+                ))
+            });
+            values.push(("Graham Value", value));
+        }
+
+        if values.is_empty() {
+            println!("Provide a book value, a growth rate or both.");
+            return;
+        }
+
+        for (name, value) in values.iter() {
+            println!("{}: {:.2}", name, value);
+        }
+
+        if let Some(price) = self.price {
+            println!("Price: {:.2}", price);
+
+            for (name, value) in values.iter() {
+                match margin_of_safety(*value, price) {
+                    Some(margin) => println!("Margin of Safety ({}): {:.1}%", name, margin * 100.0),
+                    None => println!("Margin of Safety ({}): n/a", name),
+                }
+            }
+        }
+    }
+}
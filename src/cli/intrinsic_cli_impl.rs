@@ -1,64 +1,446 @@
-use super::port::Run;
+use super::intrinsic_tui::{Failure, InteractiveView};
+use super::port::{exit_with_error, load_config, Run};
+use crate::core::batch_builder::{BatchBuilder, Company};
+use crate::core::cashflow_history_builder::{CashflowHistoryBuilder, Normalization};
+use crate::core::company_facts::CompanyFacts;
+use crate::core::dilution::{Convertible, StockOption};
 use crate::core::growth_assumption_builder::{GrowthAssumption, GrowthAssumptionBuilder};
-use crate::core::intrinsic_builder::IntrinsicBuilder;
+use crate::core::intrinsic_builder::{Adjustment, IntrinsicBuilder};
+use crate::core::market_data::{CsvMarketData, MarketDataProvider};
+use crate::core::wacc_builder::DiscountRate;
+use crate::utils::financial::nominal_rate;
+use crate::utils::grammar::{parse_multiple, parse_probability};
+use crate::utils::money::Scale;
+use crate::utils::storage::data_dir;
+use crate::utils::table::OutputFormat;
 use clap::Parser;
+use std::fs;
+use std::path::PathBuf;
 #[derive(Parser, Debug)]
 #[command(about = "Computes the intrinsic value of an asset by providing different parameters")]
 pub struct IntrinsicCliImpl {
     #[arg(
         short = 'f',
         long = "cashflow",
-        required = true,
+        required_unless_present_any = ["cashflow_history", "batch", "facts", "ticker"],
+        conflicts_with = "cashflow_history",
         help = "Current value of the asset"
     )]
-    cashflow: f32,
-    #[arg(short, long, default_value = "0.15", help = "Discount rate")]
-    rate: f32,
+    cashflow: Option<f32>,
+    #[arg(
+        long,
+        value_delimiter = ',',
+        allow_hyphen_values = true,
+        help = "Cash flow history from the oldest to the latest year, ex: 8.1,9.4,7.2,10.0,9.8"
+    )]
+    cashflow_history: Vec<f32>,
+    #[arg(
+        long,
+        default_value = "average",
+        help = "Normalization of the cash flow history: average, median, trimmed-mean, cagr-trend"
+    )]
+    normalization: Normalization,
+    #[arg(
+        short,
+        long,
+        help = "Discount rate, or wacc:rf=0.04,erp=0.055,beta=1.1,kd=0.05,tax=0.21,wd=0.3, defaults to the config file or 0.15"
+    )]
+    rate: Option<DiscountRate>,
     #[arg(
         short,
         long,
-        required = true,
-        help = "Growth assumptions in the format years,rate,growth_incr"
+        required_unless_present = "batch",
+        help = "Growth stages in the format \"5y @ 12% -1%/y\", years=5,rate=12%,step=-1% or 5,0.12,-0.01"
     )]
-    growth_assumptions: Vec<String>,
+    growth_assumptions: Vec<GrowthAssumption>,
     #[arg(short, long, help = "Optional cash amount")]
     cash: Option<f32>,
     #[arg(short, long, help = "Optional debt amount")]
     debt: Option<f32>,
+    #[arg(long, help = "Optional preferred equity")]
+    preferred: Option<f32>,
+    #[arg(long, help = "Optional lease liabilities")]
+    leases: Option<f32>,
+    #[arg(long, help = "Optional pension deficit")]
+    pension_deficit: Option<f32>,
+    #[arg(long, help = "Optional non-operating assets")]
+    non_operating_assets: Option<f32>,
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        help = "Named adjustment in the format \"label:amount\", ex: \"Minority interest:-1.2\""
+    )]
+    adjust: Vec<Adjustment>,
     #[arg(short, long, help = "Optional probability of failure")]
     probability_of_failure: Option<f32>,
+    #[arg(
+        long,
+        conflicts_with = "probability_of_failure",
+        value_parser = parse_probability,
+        help = "Optional annual probability of failure applied to each projected year, from 0 to below 1"
+    )]
+    hazard_rate: Option<f32>,
+    #[arg(
+        long,
+        requires = "hazard_rate",
+        help = "Optional value recovered in the year of failure"
+    )]
+    recovery_value: Option<f32>,
     #[arg(short, long, help = "Optional shares outstanding")]
     shares_outstanding: Option<f32>,
+    #[arg(
+        long,
+        allow_hyphen_values = true,
+        help = "Optional annual share count change, negative for buybacks, ex: -0.02"
+    )]
+    share_change_rate: Option<f32>,
+    #[arg(long, help = "Options or warrants in the format count,strike")]
+    options: Vec<StockOption>,
+    #[arg(long, help = "Convertible debt in the format amount,conversion_price")]
+    convertible: Vec<Convertible>,
+    #[arg(
+        long,
+        help = "Optional share price, used for dilution and margin of safety"
+    )]
+    price: Option<f32>,
+    #[arg(
+        short,
+        long,
+        help = "Opens a terminal view to adjust the rate, growth, cash, debt and failure live"
+    )]
+    interactive: bool,
+    #[arg(
+        long,
+        conflicts_with_all = ["interactive", "cashflow", "cashflow_history"],
+        help = "CSV file with ticker,cashflow,rate,growth,cash,debt,probability_of_failure,shares,price"
+    )]
+    batch: Option<String>,
+    #[arg(
+        long,
+        help = "Output of the batch: table or csv, defaults to the config file or table"
+    )]
+    format: Option<OutputFormat>,
+    #[arg(
+        long,
+        conflicts_with = "batch",
+        help = "SEC companyfacts JSON file prefilling the cash flow history, cash, debt and shares"
+    )]
+    facts: Option<String>,
+    #[arg(
+        long,
+        default_value_t = 5,
+        help = "Fiscal years of free cash flow history imported from the facts"
+    )]
+    facts_years: usize,
+    #[arg(
+        long,
+        default_value_t = 1e9,
+        help = "Divisor applied to the imported amounts and shares, billions by default"
+    )]
+    facts_scale: f64,
+    #[arg(
+        long,
+        conflicts_with = "batch",
+        help = "Ticker prefilling the price, shares and cash flow history from the market data"
+    )]
+    ticker: Option<String>,
+    #[arg(
+        long,
+        requires = "ticker",
+        help = "Directory with quotes.csv and history.csv, defaults to the market directory of DHANDHO_HOME"
+    )]
+    market_data: Option<String>,
+    #[arg(
+        long,
+        help = "Optional ISO code of the currency of the amounts, ex: EUR, defaults to the config file"
+    )]
+    currency: Option<String>,
+    #[arg(
+        long,
+        help = "Optional unit of the amounts and share counts: thousands, millions, billions"
+    )]
+    scale: Option<Scale>,
+    #[arg(
+        long,
+        requires = "fx_rate",
+        help = "Optional ISO code of the currency the share price is quoted in, ex: USD"
+    )]
+    price_currency: Option<String>,
+    #[arg(
+        long,
+        requires = "price_currency",
+        help = "Units of the price currency per unit of the amounts currency, ex: 1.08 for EUR/USD"
+    )]
+    fx_rate: Option<f32>,
+    #[arg(
+        long,
+        help = "Optional annual inflation, ex: 0.03, the results are also shown in real terms"
+    )]
+    inflation: Option<f32>,
+    #[arg(
+        long,
+        requires = "inflation",
+        conflicts_with = "interactive",
+        help = "The discount rate and growth are real, converted to nominal with the inflation"
+    )]
+    real: bool,
+    #[arg(
+        long,
+        conflicts_with_all = ["interactive", "batch"],
+        help = "Narrates every step of the valuation after the tables"
+    )]
+    explain: bool,
+    #[arg(
+        long,
+        value_parser = parse_multiple,
+        help = "Optional EV/FCF multiple of the last projected cash flow used as terminal value instead of the perpetual growth, ex: 12x"
+    )]
+    exit_multiple: Option<f32>,
 }
 
-impl Run for IntrinsicCliImpl {
-    fn run(self) {
-        let mut gab = GrowthAssumptionBuilder::new();
-
-        let assumptions = self
-            .growth_assumptions
-            .into_iter()
-            .map(|item| {
-                let s = item.split(",").collect::<Vec<_>>();
-                let years = s.get(0).unwrap().parse::<f32>().unwrap();
-                let rate = s.get(1).unwrap().parse::<f32>().unwrap();
-                let growth_incr = s.get(2).unwrap_or(&"0.0").parse::<f32>().unwrap();
-                GrowthAssumption(years as u8, rate, Some(growth_incr))
-            })
-            .map(|item| item.normalize())
-            .flatten()
-            .collect::<Vec<_>>();
-
-        gab.assumptions = assumptions;
+/// Expands growth stages into one assumption per year
+pub fn growth_assumptions(stages: Vec<GrowthAssumption>) -> GrowthAssumptionBuilder {
+    stages
+        .into_iter()
+        .fold(GrowthAssumptionBuilder::new(), |gab, stage| gab.add(stage))
+}
+
+impl IntrinsicCliImpl {
+    /// Whether the arguments value a single company without the interactive view
+    pub fn is_single_run(&self) -> bool {
+        self.batch.is_none() && !self.interactive
+    }
+
+    /// Builder with every assumption given on the command line
+    pub fn builder(self) -> IntrinsicBuilder {
+        let config = load_config();
+        let mut gab = growth_assumptions(self.growth_assumptions);
+        let mut rate = match self.rate {
+            // the table of a single valuation shows how the rate is built, the interactive
+            // view would leave it behind on the normal screen
+            Some(DiscountRate::Wacc(wacc)) if !self.interactive => wacc.execute(),
+            Some(rate) => rate.resolve(),
+            None => config.discount_rate(),
+        };
+
+        // the projection is always nominal, Fisher equation
+        if let (true, Some(inflation)) = (self.real, self.inflation) {
+            rate = nominal_rate(rate, inflation);
+            gab.assumptions = gab
+                .assumptions
+                .iter()
+                .map(|&growth| nominal_rate(growth, inflation))
+                .collect();
+        }
+
+        // figures from the filings, the ones given on the command line take precedence
+        let facts = self.facts.map(|path| {
+            let facts = read_company_facts(&path);
+            println!("Imported {} from {}", facts.entity_name, path);
+
+            facts.defaults(self.facts_years, self.facts_scale)
+        });
+        let market: Option<(Box<dyn MarketDataProvider>, String)> = self.ticker.map(|ticker| {
+            let dir = match self.market_data {
+                Some(dir) => PathBuf::from(dir),
+                None => data_dir().join("market"),
+            };
+            let provider: Box<dyn MarketDataProvider> = Box::new(
+                CsvMarketData::new(&dir).unwrap_or_else(|error| exit_with_error(error)),
+            );
+
+            (provider, ticker)
+        });
+
+        let mut history = self.cashflow_history;
+        if self.cashflow.is_none() && history.is_empty() {
+            history = match (&facts, &market) {
+                (Some(facts), _) if !facts.history.is_empty() => facts.history.clone(),
+                (_, Some((provider, ticker))) => provider.cashflow_history(ticker),
+                _ => vec![],
+            };
+        }
+        let cash = self.cash.or(facts.as_ref().and_then(|facts| facts.cash));
+        let debt = self.debt.or(facts.as_ref().and_then(|facts| facts.debt));
+        let shares_outstanding = self
+            .shares_outstanding
+            .or(facts.as_ref().and_then(|facts| facts.shares_outstanding))
+            .or(market
+                .as_ref()
+                .and_then(|(provider, ticker)| provider.shares_outstanding(ticker)));
+        let price = self.price.or(market
+            .as_ref()
+            .and_then(|(provider, ticker)| provider.price(ticker)));
+
+        let history_label = history
+            .iter()
+            .map(|value| value.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let years = history.len();
+        let cashflow_history = if history.is_empty() {
+            None
+        } else {
+            Some(
+                CashflowHistoryBuilder::new()
+                    .add_history(history)
+                    .add_normalization(self.normalization),
+            )
+        };
+
+        let cashflow = match &cashflow_history {
+            Some(history) => history.normalize().unwrap_or_else(|| {
+                if years < 2 {
+                    exit_with_error(format!(
+                        "cagr-trend needs at least two years of cash flows, got {}",
+                        history_label
+                    ))
+                }
+                exit_with_error(format!(
+                    "cagr-trend needs a positive first and last cash flow, got {}",
+                    history_label
+                ))
+            }),
+            None => self.cashflow.unwrap_or_else(|| {
+                exit_with_error("no cash flow given and none found in the facts or the market data")
+            }),
+        };
 
         IntrinsicBuilder::new()
-            .add_current_value(self.cashflow)
+            .add_current_value(cashflow)
+            .add_cashflow_history(cashflow_history)
             .add_growth_assumptions(gab)
-            .add_rate(self.rate)
-            .add_cash(self.cash)
-            .add_debt(self.debt)
+            .add_rate(rate)
+            .add_cash(cash)
+            .add_debt(debt)
+            .add_preferred(self.preferred)
+            .add_leases(self.leases)
+            .add_pension_deficit(self.pension_deficit)
+            .add_non_operating_assets(self.non_operating_assets)
+            .add_adjustments(self.adjust)
             .add_probability_of_failure(self.probability_of_failure)
-            .add_shares_outstanding(self.shares_outstanding)
-            .execute();
+            .add_hazard_rate(self.hazard_rate)
+            .add_recovery_value(self.recovery_value)
+            .add_shares_outstanding(shares_outstanding)
+            .add_share_change_rate(self.share_change_rate)
+            .add_options(self.options)
+            .add_convertibles(self.convertible)
+            .add_share_price(price)
+            .add_currency(self.currency.or(config.currency))
+            .add_scale(self.scale)
+            .add_price_currency(self.price_currency)
+            .add_fx_rate(self.fx_rate)
+            .add_inflation(self.inflation)
+            .add_precision(config.precision)
+            .add_exit_multiple(self.exit_multiple)
+            .add_explain(self.explain)
+    }
+}
+
+/// Reads a companyfacts JSON file downloaded from the SEC
+pub fn read_company_facts(path: &str) -> CompanyFacts {
+    let content = fs::read_to_string(path).unwrap_or_else(|error| {
+        exit_with_error(format!("cannot read the facts file {}: {}", path, error))
+    });
+
+    serde_json::from_str(&content).unwrap_or_else(|error| {
+        exit_with_error(format!("invalid companyfacts file {}: {}", path, error))
+    })
+}
+
+/// Reads the companies of a batch CSV file with headers, the invalid rows are reported and skipped
+pub fn read_companies(path: &str) -> Vec<Company> {
+    let mut reader = csv::Reader::from_path(path).unwrap_or_else(|error| {
+        exit_with_error(format!("cannot read the batch file {}: {}", path, error))
+    });
+    let headers = reader
+        .headers()
+        .cloned()
+        .unwrap_or_else(|error| exit_with_error(format!("invalid batch file {}: {}", path, error)));
+
+    reader
+        .records()
+        .filter_map(|record| {
+            let company = record
+                .map_err(|error| error.to_string())
+                .and_then(|record| {
+                    record
+                        .deserialize::<Company>(Some(&headers))
+                        .map_err(|error| {
+                            format!("{}: {}", record.get(0).unwrap_or_default(), error)
+                        })
+                });
+
+            company
+                .inspect_err(|error| eprintln!("skipped {}", error))
+                .ok()
+        })
+        .collect()
+}
+
+impl Run for IntrinsicCliImpl {
+    fn run(self) {
+        if let Some(path) = self.batch {
+            let config = load_config();
+            let rate = match self.rate {
+                Some(rate) => rate.resolve(),
+                None => config.discount_rate(),
+            };
+
+            read_companies(&path)
+                .into_iter()
+                .filter_map(|company| {
+                    let stages = company
+                        .growth
+                        .split(';')
+                        .map(|stage| stage.trim().parse::<GrowthAssumption>())
+                        .collect::<Result<Vec<_>, _>>();
+
+                    match stages {
+                        Ok(stages) => Some((company, stages)),
+                        Err(error) => {
+                            eprintln!("skipped {}: invalid growth, {}", company.ticker, error);
+                            None
+                        }
+                    }
+                })
+                .fold(BatchBuilder::new(), |batch, (company, stages)| {
+                    let builder = IntrinsicBuilder::new()
+                        .add_current_value(company.cashflow)
+                        .add_rate(company.rate.unwrap_or(rate))
+                        .add_growth_assumptions(growth_assumptions(stages))
+                        .add_cash(company.cash)
+                        .add_debt(company.debt)
+                        .add_probability_of_failure(company.probability_of_failure)
+                        .add_shares_outstanding(company.shares)
+                        .add_share_price(company.price);
+
+                    batch.add_company(company.ticker, builder)
+                })
+                .add_format(self.format.unwrap_or(config.format()))
+                .add_currency(self.currency.or(config.currency))
+                .add_precision(config.precision.unwrap_or(2))
+                .execute();
+
+            return;
+        }
+
+        if !self.interactive {
+            self.builder().execute();
+
+            return;
+        }
+
+        let stages = self.growth_assumptions.clone();
+        let failure = match self.hazard_rate {
+            Some(hazard_rate) => Failure::Hazard(hazard_rate),
+            None => Failure::Probability(self.probability_of_failure.unwrap_or(0.0)),
+        };
+
+        InteractiveView::new(self.builder(), stages, failure)
+            .run()
+            .unwrap_or_else(|error| {
+                exit_with_error(format!("cannot run the interactive view: {}", error))
+            });
     }
 }
@@ -0,0 +1,375 @@
+use crate::core::growth_assumption_builder::{GrowthAssumption, GrowthAssumptionBuilder};
+use crate::core::intrinsic_builder::IntrinsicBuilder;
+use crate::utils::table::new_table;
+use comfy_table::Cell;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, terminal};
+use std::io::{self, Write};
+
+/// Failure assumption adjusted by the view, whichever one was given on the command line
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Failure {
+    Probability(f32),
+    Hazard(f32),
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+enum Field {
+    Rate,
+    StageYears(usize),
+    StageRate(usize),
+    StageIncrement(usize),
+    Cash,
+    Debt,
+    Failure,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+struct Assumptions {
+    rate: f32,
+    stages: Vec<GrowthAssumption>,
+    cash: Option<f32>,
+    debt: Option<f32>,
+    failure: Failure,
+}
+
+/// Terminal view recomputing the valuation on every key press
+pub struct InteractiveView {
+    // every other input stays as given on the command line
+    base: IntrinsicBuilder,
+    initial: Assumptions,
+    current: Assumptions,
+    selected: usize,
+    // step used for cash and debt, a tenth of the current cash flow
+    amount_step: f32,
+}
+
+impl InteractiveView {
+    pub fn new(
+        base: IntrinsicBuilder,
+        stages: Vec<GrowthAssumption>,
+        failure: Failure,
+    ) -> InteractiveView {
+        let assumptions = Assumptions {
+            rate: base.rate(),
+            stages,
+            cash: base.cash(),
+            debt: base.debt(),
+            failure,
+        };
+
+        InteractiveView {
+            amount_step: (base.current_value().abs() / 10.0).max(0.1),
+            base,
+            initial: assumptions.clone(),
+            current: assumptions,
+            selected: 0,
+        }
+    }
+
+    fn fields(&self) -> Vec<Field> {
+        let mut fields = vec![Field::Rate];
+        for index in 0..self.current.stages.len() {
+            fields.push(Field::StageYears(index));
+            fields.push(Field::StageRate(index));
+            fields.push(Field::StageIncrement(index));
+        }
+        fields.extend([Field::Cash, Field::Debt, Field::Failure]);
+
+        fields
+    }
+
+    fn growth_assumptions(&self) -> GrowthAssumptionBuilder {
+        self.current
+            .stages
+            .iter()
+            .fold(GrowthAssumptionBuilder::new(), |gab, &stage| gab.add(stage))
+    }
+
+    /// A perpetual growth at or above the discount rate makes the terminal value negative or infinite
+    fn is_terminal_growth_valid(&self) -> bool {
+        let terminal_growth = self.growth_assumptions().assumptions.last().copied();
+
+        self.base.exit_multiple().is_some()
+            || terminal_growth.is_none_or(|growth| growth < self.current.rate)
+    }
+
+    fn builder(&self) -> IntrinsicBuilder {
+        let builder = self
+            .base
+            .clone()
+            .add_rate(self.current.rate)
+            .add_growth_assumptions(self.growth_assumptions())
+            .add_cash(self.current.cash)
+            .add_debt(self.current.debt);
+
+        match self.current.failure {
+            Failure::Probability(probability) => {
+                builder.add_probability_of_failure((probability > 0.0).then_some(probability))
+            }
+            Failure::Hazard(hazard_rate) => builder.add_hazard_rate(Some(hazard_rate)),
+        }
+    }
+
+    /// Moves the selected assumption one step up or down, a step bringing the terminal growth
+    /// to the discount rate is refused
+    fn adjust(&mut self, direction: f32) {
+        let previous = self.current.clone();
+        let was_valid = self.is_terminal_growth_valid();
+        self.step(direction);
+
+        if was_valid && !self.is_terminal_growth_valid() {
+            self.current = previous;
+        }
+    }
+
+    fn step(&mut self, direction: f32) {
+        let step = self.amount_step;
+        let field = self.fields()[self.selected];
+        let current = &mut self.current;
+
+        match field {
+            Field::Rate => current.rate = (current.rate + 0.005 * direction).max(0.005),
+            Field::StageYears(index) => {
+                let stage = &mut current.stages[index];
+                stage.0 = (stage.0 as f32 + direction).clamp(1.0, 50.0) as u8;
+            }
+            // growth stages move one percent at a time
+            Field::StageRate(index) => current.stages[index].1 += 0.01 * direction,
+            Field::StageIncrement(index) => {
+                let stage = &mut current.stages[index];
+                stage.2 = Some(stage.2.unwrap_or(0.0) + 0.01 * direction);
+            }
+            Field::Cash => {
+                current.cash = Some((current.cash.unwrap_or(0.0) + step * direction).max(0.0))
+            }
+            Field::Debt => {
+                current.debt = Some((current.debt.unwrap_or(0.0) + step * direction).max(0.0))
+            }
+            Field::Failure => {
+                current.failure = match current.failure {
+                    Failure::Probability(probability) => {
+                        Failure::Probability((probability + 0.01 * direction).clamp(0.0, 0.99))
+                    }
+                    Failure::Hazard(hazard_rate) => {
+                        Failure::Hazard((hazard_rate + 0.005 * direction).clamp(0.0, 0.99))
+                    }
+                }
+            }
+        }
+    }
+
+    fn render(&self) -> String {
+        let (valuation, tables) = self.builder().report();
+        let money = self.base.money();
+
+        let mut controls_table = new_table(vec!["", "Assumption", "Value"], vec![3, 22, 14]);
+
+        for (index, field) in self.fields().into_iter().enumerate() {
+            let (label, value) = match field {
+                Field::Rate => (
+                    "Discount Rate".to_string(),
+                    format!("{:.1}%", self.current.rate * 100.0),
+                ),
+                Field::StageYears(stage) => (
+                    format!("Stage {} Years", stage + 1),
+                    format!("{}", self.current.stages[stage].0),
+                ),
+                Field::StageRate(stage) => (
+                    format!("Stage {} Growth", stage + 1),
+                    format!("{:.1}%", self.current.stages[stage].1 * 100.0),
+                ),
+                Field::StageIncrement(stage) => (
+                    format!("Stage {} Step", stage + 1),
+                    format!(
+                        "{:+.1}%",
+                        self.current.stages[stage].2.unwrap_or(0.0) * 100.0
+                    ),
+                ),
+                Field::Cash => (
+                    "Cash".to_string(),
+                    money.format(self.current.cash.unwrap_or(0.0)),
+                ),
+                Field::Debt => (
+                    "Debt".to_string(),
+                    money.format(self.current.debt.unwrap_or(0.0)),
+                ),
+                Field::Failure => match self.current.failure {
+                    Failure::Probability(probability) => (
+                        "Failure Probability".to_string(),
+                        format!("{:.0}%", probability * 100.0),
+                    ),
+                    Failure::Hazard(hazard_rate) => (
+                        "Hazard Rate".to_string(),
+                        format!("{:.1}%", hazard_rate * 100.0),
+                    ),
+                },
+            };
+
+            let marker = if index == self.selected { ">" } else { "" };
+            controls_table.add_row(vec![Cell::new(marker), Cell::new(label), Cell::new(value)]);
+        }
+
+        let summary = match valuation.per_share {
+            Some(per_share) => format!(
+                "Intrinsic Value: {}   per Share: {}",
+                money.format(valuation.intrinsic),
+                self.base.per_share_money().format(per_share)
+            ),
+            None => format!("Intrinsic Value: {}", money.format(valuation.intrinsic)),
+        };
+
+        format!(
+            "\n{}\n{}\n\n{}\n↑/↓ select  ←/→ adjust  r reset  q quit\n",
+            tables, summary, controls_table
+        )
+    }
+
+    /// Runs the view until the analyst quits, restoring the terminal afterwards
+    pub fn run(mut self) -> io::Result<()> {
+        let mut stdout = io::stdout();
+
+        terminal::enable_raw_mode()?;
+        execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+        let result = self.event_loop(&mut stdout);
+
+        execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+        terminal::disable_raw_mode()?;
+
+        result
+    }
+
+    fn event_loop(&mut self, stdout: &mut io::Stdout) -> io::Result<()> {
+        loop {
+            queue!(
+                stdout,
+                terminal::Clear(terminal::ClearType::All),
+                cursor::MoveTo(0, 0)
+            )?;
+            // raw mode does not return the carriage on a new line
+            write!(stdout, "{}", self.render().replace('\n', "\r\n"))?;
+            stdout.flush()?;
+
+            let Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) = event::read()?
+            else {
+                continue;
+            };
+
+            if !self.handle_key(code, modifiers) {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Applies a key press, false when the analyst quits
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> bool {
+        let fields = self.fields().len();
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = (self.selected + fields - 1) % fields
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1) % fields,
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('-') => self.adjust(-1.0),
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('+') => self.adjust(1.0),
+            KeyCode::Char('r') => self.current = self.initial.clone(),
+            _ => {}
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_view(failure: Failure) -> InteractiveView {
+        let base = IntrinsicBuilder::new()
+            .add_current_value(10.0)
+            .add_rate(0.1);
+
+        InteractiveView::new(base, vec![GrowthAssumption(5, 0.05, None)], failure)
+    }
+
+    #[test]
+    fn test_keys_select_adjust_and_reset_the_assumptions() {
+        let mut view = new_view(Failure::Probability(0.0));
+
+        assert!(view.handle_key(KeyCode::Right, KeyModifiers::NONE));
+        assert_eq!(view.current.rate, 0.105000004);
+
+        // up from the first field wraps to the failure probability
+        view.handle_key(KeyCode::Up, KeyModifiers::NONE);
+        assert_eq!(view.fields()[view.selected], Field::Failure);
+        view.handle_key(KeyCode::Char('+'), KeyModifiers::NONE);
+        assert_eq!(view.current.failure, Failure::Probability(0.01));
+
+        view.handle_key(KeyCode::Char('r'), KeyModifiers::NONE);
+        assert_eq!(view.current, view.initial);
+        assert!(!view.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert!(!view.handle_key(KeyCode::Char('q'), KeyModifiers::NONE));
+    }
+
+    #[test]
+    fn test_builder_is_rebuilt_from_the_current_assumptions() {
+        let mut view = new_view(Failure::Hazard(0.02));
+        // cash is the field after the three of the single stage
+        view.selected = 4;
+        view.adjust(1.0);
+
+        assert_eq!(
+            view.builder(),
+            view.base
+                .clone()
+                .add_growth_assumptions(
+                    GrowthAssumptionBuilder::new().add(GrowthAssumption(5, 0.05, None))
+                )
+                .add_cash(Some(1.0))
+                .add_hazard_rate(Some(0.02))
+        );
+        // a zero probability of failure is no failure at all
+        let view = new_view(Failure::Probability(0.0));
+        assert_eq!(
+            view.builder(),
+            view.base.clone().add_growth_assumptions(
+                GrowthAssumptionBuilder::new().add(GrowthAssumption(5, 0.05, None))
+            )
+        );
+    }
+
+    #[test]
+    fn test_stage_growth_stays_below_the_discount_rate() {
+        let mut view = new_view(Failure::Probability(0.0));
+        view.selected = 2;
+
+        for _ in 0..10 {
+            view.adjust(1.0);
+        }
+
+        assert_eq!(view.current.stages[0].1, 0.089999996);
+    }
+
+    #[test]
+    fn test_summary_is_in_the_currency_of_the_amounts() {
+        let base = IntrinsicBuilder::new()
+            .add_current_value(10.0)
+            .add_rate(0.1)
+            .add_currency(Some("EUR".to_string()));
+        let view = InteractiveView::new(
+            base,
+            vec![GrowthAssumption(5, 0.05, None)],
+            Failure::Probability(0.0),
+        );
+
+        assert!(view.render().contains("Intrinsic Value: €"));
+    }
+}
@@ -0,0 +1,162 @@
+use super::intrinsic_cli_impl::IntrinsicCliImpl;
+use super::port::{exit_with_error, Run};
+use crate::core::journal::{attribute, value_of, JournalEntry};
+use crate::utils::date::{format_timestamp, now};
+use crate::utils::storage::{append_line, data_dir, read_lines};
+use crate::utils::table::{new_table, print_side_by_side};
+use clap::{Parser, Subcommand};
+use comfy_table::Cell;
+use std::path::{Path, PathBuf};
+
+#[derive(Parser, Debug)]
+#[command(about = "Saves intrinsic valuations, lists them and compares two runs")]
+pub struct JournalCliImpl {
+    #[command(subcommand)]
+    command: JournalCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum JournalCommand {
+    /// Runs an intrinsic valuation and saves its inputs and outputs
+    Save {
+        #[arg(value_name = "TICKER", help = "Ticker of the valued company")]
+        company: String,
+        #[arg(long, help = "Optional note on the thesis")]
+        note: Option<String>,
+        #[command(flatten)]
+        intrinsic: Box<IntrinsicCliImpl>,
+    },
+    /// Lists the saved valuations, optionally of a single ticker
+    List {
+        #[arg(help = "Optional ticker to filter by")]
+        ticker: Option<String>,
+    },
+    /// Shows which assumptions changed between two runs and how much each moved the value
+    Diff {
+        #[arg(help = "Number of the earlier run, as shown by list")]
+        from: usize,
+        #[arg(help = "Number of the later run, as shown by list")]
+        to: usize,
+    },
+}
+
+pub fn journal_path() -> PathBuf {
+    data_dir().join("journal.jsonl")
+}
+
+fn entry(entries: &[JournalEntry], number: usize) -> &JournalEntry {
+    number
+        .checked_sub(1)
+        .and_then(|index| entries.get(index))
+        .unwrap_or_else(|| exit_with_error(format!("no journal entry #{}", number)))
+}
+
+fn read_entries(path: &Path) -> Vec<JournalEntry> {
+    read_lines(path).unwrap_or_else(|error| exit_with_error(error))
+}
+
+impl Run for JournalCliImpl {
+    fn run(self) {
+        let path = journal_path();
+
+        match self.command {
+            JournalCommand::Save {
+                company,
+                note,
+                intrinsic,
+            } => {
+                if !intrinsic.is_single_run() {
+                    exit_with_error(
+                        "journal save runs a single valuation, without --batch or --interactive",
+                    )
+                }
+
+                let builder = intrinsic.builder();
+                builder.clone().execute();
+
+                let entry = JournalEntry::new(company, now(), note, builder);
+                append_line(&path, &entry);
+
+                let number = read_entries(&path).len();
+                println!("Saved #{} {} to {}", number, entry.ticker, path.display());
+            }
+            JournalCommand::List { ticker } => {
+                let entries = read_entries(&path);
+
+                let mut table = new_table(
+                    vec!["#", "Date", "Ticker", "Value", "per Share", "Note"],
+                    vec![5, 18, 8, 11, 11, 30],
+                );
+
+                for (index, entry) in entries.iter().enumerate() {
+                    if ticker
+                        .as_ref()
+                        .is_some_and(|ticker| !ticker.eq_ignore_ascii_case(&entry.ticker))
+                    {
+                        continue;
+                    }
+
+                    table.add_row(vec![
+                        Cell::new(index + 1),
+                        Cell::new(format_timestamp(entry.timestamp)),
+                        Cell::new(&entry.ticker),
+                        Cell::new(format!("${:.2}", entry.intrinsic)),
+                        Cell::new(match entry.per_share {
+                            Some(per_share) => format!("${:.2}", per_share),
+                            None => "".to_string(),
+                        }),
+                        Cell::new(entry.note.as_deref().unwrap_or("")),
+                    ]);
+                }
+
+                print_side_by_side(&[&table]);
+            }
+            JournalCommand::Diff { from, to } => {
+                let entries = read_entries(&path);
+                let (from_entry, to_entry) = (entry(&entries, from), entry(&entries, to));
+                let start = value_of(&from_entry.inputs);
+                let end = value_of(&to_entry.inputs);
+
+                for (number, entry) in [(from, from_entry), (to, to_entry)] {
+                    println!(
+                        "#{} {} {} {}",
+                        number,
+                        entry.ticker,
+                        format_timestamp(entry.timestamp),
+                        entry.note.as_deref().unwrap_or("")
+                    );
+                }
+
+                let mut table = new_table(
+                    vec!["Assumption", "From", "To", "Impact"],
+                    vec![20, 28, 28, 10],
+                );
+
+                table.add_row(vec![
+                    Cell::new(format!("#{} {}", from, from_entry.ticker)),
+                    Cell::new(""),
+                    Cell::new(""),
+                    Cell::new(format!("${:.2}", start)),
+                ]);
+
+                for attribution in attribute(&from_entry.inputs, &to_entry.inputs) {
+                    table.add_row(vec![
+                        Cell::new(attribution.assumption),
+                        Cell::new(attribution.from),
+                        Cell::new(attribution.to),
+                        Cell::new(format!("{:+.2}", attribution.impact)),
+                    ]);
+                }
+
+                table.add_row(vec![
+                    Cell::new(format!("= #{} {}", to, to_entry.ticker)),
+                    Cell::new(""),
+                    Cell::new(""),
+                    Cell::new(format!("${:.2}", end)),
+                ]);
+
+                print_side_by_side(&[&table]);
+            }
+        }
+    }
+}
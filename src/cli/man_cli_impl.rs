@@ -0,0 +1,47 @@
+use super::port::{exit_with_error, Run};
+use crate::Dhandho;
+use clap::{CommandFactory, Parser};
+use clap_mangen::Man;
+use std::io;
+
+#[derive(Parser, Debug)]
+#[command(about = "Prints the man page, or writes one page per subcommand to a directory")]
+pub struct ManCliImpl {
+    #[arg(
+        long,
+        help = "Optional directory receiving dhandho.1 and one dhandho-<subcommand>.1 per subcommand"
+    )]
+    out_dir: Option<String>,
+}
+
+impl Run for ManCliImpl {
+    fn run(self) {
+        let command = Dhandho::command();
+
+        match self.out_dir {
+            Some(dir) => {
+                clap_mangen::generate_to(command, &dir).unwrap_or_else(|error| {
+                    exit_with_error(format!("cannot write the man pages to {}: {}", dir, error))
+                });
+                println!("Man pages written to {}", dir);
+            }
+            None => Man::new(command)
+                .render(&mut io::stdout())
+                .expect("cannot write the man page"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_man_page_is_rendered() {
+        let mut page = Vec::new();
+
+        Man::new(Dhandho::command()).render(&mut page).unwrap();
+
+        assert!(!page.is_empty());
+    }
+}
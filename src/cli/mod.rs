@@ -1,4 +1,16 @@
 pub mod cagr_cli_impl;
+pub mod comps_cli_impl;
+pub mod completions_cli_impl;
+pub mod ddm_cli_impl;
+pub mod epv_cli_impl;
+pub mod graham_cli_impl;
 pub mod intrinsic_cli_impl;
+pub mod intrinsic_tui;
+pub mod journal_cli_impl;
 pub mod kelly_cli_impl;
+pub mod man_cli_impl;
 pub mod port;
+pub mod portfolio_cli_impl;
+pub mod residual_income_cli_impl;
+pub mod wacc_cli_impl;
+pub mod watchlist_cli_impl;
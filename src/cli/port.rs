@@ -1,3 +1,21 @@
+use crate::utils::config::Config;
+use crate::Dhandho;
+use clap::error::ErrorKind;
+use clap::CommandFactory;
+use std::fmt::Display;
+
 pub trait Run {
     fn run(self);
 }
+
+/// Exits like clap does on an invalid argument, for the values only checked once parsed
+pub fn exit_with_error(message: impl Display) -> ! {
+    Dhandho::command()
+        .error(ErrorKind::ValueValidation, message)
+        .exit()
+}
+
+/// Config file of the user, an invalid one stops the command with the reason
+pub fn load_config() -> Config {
+    Config::load().unwrap_or_else(|error| exit_with_error(error))
+}
@@ -0,0 +1,75 @@
+use super::cagr_cli_impl::TaxArgs;
+use super::port::{exit_with_error, load_config, Run};
+use crate::core::portfolio_builder::{PortfolioBuilder, Position};
+use crate::utils::date::{now, parse_date};
+use clap::Parser;
+use serde::Deserialize;
+use std::fs;
+
+#[derive(Parser, Debug)]
+#[command(about = "Reports the returns, weights and kelly sizing of the portfolio holdings")]
+pub struct PortfolioCliImpl {
+    #[arg(
+        long,
+        required = true,
+        help = "TOML file with [[position]] tables: ticker, price, intrinsic, scenarios and lots"
+    )]
+    holdings: String,
+    #[arg(
+        long,
+        help = "Optional valuation date in the format YYYY-MM-DD, defaults to today"
+    )]
+    as_of: Option<String>,
+    #[arg(
+        short,
+        long,
+        help = "Fraction of the full kelly weight to target, ex: 0.5 for half kelly, defaults to the config file or 1"
+    )]
+    kelly_fraction: Option<f32>,
+    #[arg(
+        short,
+        long,
+        default_value_t = 0.02,
+        help = "Weight difference to the kelly target tolerated before flagging a position"
+    )]
+    tolerance: f32,
+    #[command(flatten)]
+    tax: TaxArgs,
+}
+
+#[derive(Deserialize)]
+struct HoldingsFile {
+    position: Vec<Position>,
+}
+
+/// Reads the positions of a TOML file with [[position]] tables
+pub fn read_holdings(path: &str) -> Result<Vec<Position>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|error| format!("cannot read the holdings file {}: {}", path, error))?;
+    let file: HoldingsFile = toml::from_str(&content)
+        .map_err(|error| format!("invalid holdings file {}: {}", path, error))?;
+
+    Ok(file.position)
+}
+
+impl Run for PortfolioCliImpl {
+    fn run(self) {
+        let as_of = match self.as_of {
+            Some(date) => parse_date(&date).unwrap_or_else(|error| exit_with_error(error)),
+            None => (now() / 86_400) as i64,
+        };
+
+        PortfolioBuilder::new()
+            .add_positions(
+                read_holdings(&self.holdings).unwrap_or_else(|error| exit_with_error(error)),
+            )
+            .add_as_of(as_of)
+            .add_kelly_fraction(
+                self.kelly_fraction
+                    .unwrap_or_else(|| load_config().kelly_fraction()),
+            )
+            .add_tolerance(self.tolerance)
+            .add_tax(self.tax.rates())
+            .execute();
+    }
+}
@@ -0,0 +1,52 @@
+use super::intrinsic_cli_impl::growth_assumptions;
+use super::port::{load_config, Run};
+use crate::core::growth_assumption_builder::GrowthAssumption;
+use crate::core::residual_income_builder::ResidualIncomeBuilder;
+use crate::utils::grammar::parse_rate;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(
+    about = "Values a financial by its book value plus the returns earned above the cost of equity"
+)]
+pub struct ResidualIncomeCliImpl {
+    #[arg(short, long, required = true, help = "Current book value per share")]
+    book_value: f32,
+    #[arg(
+        short,
+        long,
+        value_parser = parse_rate,
+        help = "Cost of equity, defaults to the discount rate of the config file or 0.15"
+    )]
+    rate: Option<f32>,
+    #[arg(
+        long,
+        required = true,
+        value_parser = parse_rate,
+        help = "Return on equity of the first year, fading toward the cost of equity"
+    )]
+    roe: f32,
+    #[arg(
+        short,
+        long,
+        required = true,
+        help = "Book value growth in the format \"5y @ 12% -1%/y\" or years,rate,growth_incr"
+    )]
+    growth_assumptions: Vec<GrowthAssumption>,
+    #[arg(long, help = "Optional share price for the margin of safety")]
+    price: Option<f32>,
+}
+
+impl Run for ResidualIncomeCliImpl {
+    fn run(self) {
+        let rate = self.rate.unwrap_or_else(|| load_config().discount_rate());
+
+        ResidualIncomeBuilder::new()
+            .add_book_value(self.book_value)
+            .add_rate(rate)
+            .add_roe(self.roe)
+            .add_growth_assumptions(growth_assumptions(self.growth_assumptions))
+            .add_share_price(self.price)
+            .execute();
+    }
+}
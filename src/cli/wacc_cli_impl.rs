@@ -0,0 +1,53 @@
+use super::port::Run;
+use crate::core::wacc_builder::WaccBuilder;
+use crate::utils::grammar::parse_weight;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(about = "Computes the weighted average cost of capital used as discount rate")]
+pub struct WaccCliImpl {
+    #[arg(long, required = true, help = "Risk-free rate, ex: 0.04")]
+    risk_free_rate: f32,
+    #[arg(long, required = true, help = "Equity risk premium, ex: 0.055")]
+    equity_risk_premium: f32,
+    #[arg(
+        long,
+        help = "Optional beta, without it the cost of equity is built up from premiums"
+    )]
+    beta: Option<f32>,
+    #[arg(long, help = "Optional size premium for the build-up method")]
+    size_premium: Option<f32>,
+    #[arg(long, help = "Optional company specific risk premium")]
+    specific_premium: Option<f32>,
+    #[arg(long, default_value = "0.0", help = "Pre-tax cost of debt")]
+    cost_of_debt: f32,
+    #[arg(long, default_value = "0.0", help = "Marginal tax rate")]
+    tax_rate: f32,
+    #[arg(
+        long,
+        default_value = "0.0",
+        value_parser = parse_weight,
+        help = "Debt share of the capital structure, from 0 to 1"
+    )]
+    debt_weight: f32,
+}
+
+impl WaccCliImpl {
+    fn builder(self) -> WaccBuilder {
+        WaccBuilder::new()
+            .add_risk_free_rate(self.risk_free_rate)
+            .add_equity_risk_premium(self.equity_risk_premium)
+            .add_beta(self.beta)
+            .add_size_premium(self.size_premium)
+            .add_specific_risk_premium(self.specific_premium)
+            .add_cost_of_debt(self.cost_of_debt)
+            .add_tax_rate(self.tax_rate)
+            .add_debt_weight(self.debt_weight)
+    }
+}
+
+impl Run for WaccCliImpl {
+    fn run(self) {
+        self.builder().execute();
+    }
+}
@@ -0,0 +1,137 @@
+use super::journal_cli_impl::journal_path;
+use super::port::{exit_with_error, load_config, Run};
+use crate::core::journal::{latest_per_share, JournalEntry};
+use crate::core::watchlist_builder::{Quote, WatchItem, WatchlistBuilder};
+use crate::utils::storage::{data_dir, read_lines, write_lines};
+use clap::{Parser, Subcommand};
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(about = "Screens the followed tickers for prices below their buy price")]
+pub struct WatchlistCliImpl {
+    #[command(subcommand)]
+    command: WatchlistCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum WatchlistCommand {
+    /// Follows a ticker, or updates it when already followed
+    Add {
+        #[arg(help = "Ticker to follow")]
+        ticker: String,
+        #[arg(
+            short,
+            long,
+            help = "Discount to the intrinsic value required before buying, defaults to the config file or 0.3"
+        )]
+        margin_of_safety: Option<f32>,
+        #[arg(
+            short,
+            long,
+            help = "Optional value per share, defaults to the latest journal run of the ticker"
+        )]
+        value: Option<f32>,
+    },
+    /// Stops following a ticker
+    Remove {
+        #[arg(help = "Ticker to remove")]
+        ticker: String,
+    },
+    /// Reports the tickers trading below their buy price, sorted by discount
+    Screen {
+        #[arg(
+            short,
+            long,
+            help = "CSV file with ticker,price headers, read from stdin when missing"
+        )]
+        prices: Option<String>,
+        #[arg(short, long, help = "Also report the tickers above their buy price")]
+        all: bool,
+    },
+}
+
+pub fn watchlist_path() -> PathBuf {
+    data_dir().join("watchlist.jsonl")
+}
+
+/// Reads quotes from a CSV file with ticker,price headers, or from stdin
+pub fn read_quotes(path: Option<&str>) -> Vec<Quote> {
+    let content = match path {
+        Some(path) if path != "-" => fs::read_to_string(path).unwrap_or_else(|error| {
+            exit_with_error(format!("cannot read the prices file {}: {}", path, error))
+        }),
+        _ => {
+            let mut content = String::new();
+            io::stdin()
+                .read_to_string(&mut content)
+                .unwrap_or_else(|error| {
+                    exit_with_error(format!("cannot read the prices from stdin: {}", error))
+                });
+            content
+        }
+    };
+
+    csv::Reader::from_reader(content.as_bytes())
+        .deserialize()
+        .map(|quote| {
+            quote.unwrap_or_else(|error| exit_with_error(format!("invalid prices CSV: {}", error)))
+        })
+        .collect()
+}
+
+impl Run for WatchlistCliImpl {
+    fn run(self) {
+        let path = watchlist_path();
+        let mut items: Vec<WatchItem> =
+            read_lines(&path).unwrap_or_else(|error| exit_with_error(error));
+
+        match self.command {
+            WatchlistCommand::Add {
+                ticker,
+                margin_of_safety,
+                value,
+            } => {
+                let ticker = ticker.to_uppercase();
+                let margin_of_safety =
+                    margin_of_safety.unwrap_or_else(|| load_config().margin_of_safety());
+                items.retain(|item| item.ticker != ticker);
+                items.push(WatchItem {
+                    ticker: ticker.clone(),
+                    margin_of_safety,
+                    value,
+                });
+                write_lines(&path, &items);
+
+                println!(
+                    "Watching {} with a {:.0}% margin of safety",
+                    ticker,
+                    margin_of_safety * 100.0
+                );
+            }
+            WatchlistCommand::Remove { ticker } => {
+                let count = items.len();
+                items.retain(|item| !item.ticker.eq_ignore_ascii_case(&ticker));
+                write_lines(&path, &items);
+
+                if items.len() == count {
+                    println!("{} is not on the watchlist", ticker);
+                } else {
+                    println!("Removed {}", ticker.to_uppercase());
+                }
+            }
+            WatchlistCommand::Screen { prices, all } => {
+                let entries: Vec<JournalEntry> =
+                    read_lines(&journal_path()).unwrap_or_else(|error| exit_with_error(error));
+
+                WatchlistBuilder::new()
+                    .add_items(items)
+                    .add_values(latest_per_share(&entries))
+                    .add_quotes(read_quotes(prices.as_deref()))
+                    .add_all(all)
+                    .execute();
+            }
+        }
+    }
+}
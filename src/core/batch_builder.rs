@@ -0,0 +1,166 @@
+use super::intrinsic_builder::IntrinsicBuilder;
+use crate::utils::money::Money;
+use crate::utils::table::{new_table, print_side_by_side, OutputFormat};
+use comfy_table::Cell;
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// Company of a batch file, one row per company
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+pub struct Company {
+    pub ticker: String,
+    pub cashflow: f32,
+    // optional discount rate, defaults to the one of the command line
+    pub rate: Option<f32>,
+    // growth stages in the format years,rate,growth_incr separated by `;`
+    pub growth: String,
+    pub cash: Option<f32>,
+    pub debt: Option<f32>,
+    pub probability_of_failure: Option<f32>,
+    pub shares: Option<f32>,
+    pub price: Option<f32>,
+}
+
+/// Valuation of one company of the batch
+#[derive(PartialEq, Debug, Clone, Serialize)]
+pub struct BatchRow {
+    pub ticker: String,
+    pub npv: f32,
+    pub equity_value: f32,
+    pub per_share: Option<f32>,
+    pub margin_of_safety: Option<f32>,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct BatchBuilder {
+    // (ticker, valuation inputs) of every company
+    companies: Vec<(String, IntrinsicBuilder)>,
+    format: OutputFormat,
+    // optional ISO code of the currency of the amounts
+    currency: Option<String>,
+    // decimals of the amounts of the table
+    precision: usize,
+}
+
+impl BatchBuilder {
+    pub fn new() -> BatchBuilder {
+        BatchBuilder {
+            companies: vec![],
+            format: OutputFormat::Table,
+            currency: None,
+            precision: 2,
+        }
+    }
+
+    pub fn add_company(mut self, ticker: String, builder: IntrinsicBuilder) -> BatchBuilder {
+        self.companies.push((ticker, builder));
+
+        self
+    }
+
+    pub fn add_format(mut self, format: OutputFormat) -> BatchBuilder {
+        self.format = format;
+
+        self
+    }
+
+    pub fn add_currency(mut self, currency: Option<String>) -> BatchBuilder {
+        self.currency = currency;
+
+        self
+    }
+
+    pub fn add_precision(mut self, precision: usize) -> BatchBuilder {
+        self.precision = precision;
+
+        self
+    }
+
+    pub fn rows(&self) -> Vec<BatchRow> {
+        self.companies
+            .iter()
+            .map(|(ticker, builder)| {
+                let valuation = builder.valuation();
+
+                BatchRow {
+                    ticker: ticker.clone(),
+                    npv: valuation.npv,
+                    equity_value: valuation.intrinsic,
+                    per_share: valuation.per_share,
+                    margin_of_safety: valuation.margin_of_safety,
+                }
+            })
+            .collect()
+    }
+
+    pub fn execute(self) -> Vec<BatchRow> {
+        let rows = self.rows();
+
+        match self.format {
+            OutputFormat::Csv => {
+                let mut writer = csv::Writer::from_writer(io::stdout());
+                for row in rows.iter() {
+                    writer.serialize(row).expect("cannot write the CSV row");
+                }
+                writer.flush().expect("cannot write the CSV output");
+            }
+            OutputFormat::Table => {
+                let money =
+                    Money::new(self.currency.as_deref(), None).add_precision(self.precision);
+                let mut table = new_table(
+                    vec!["Ticker", "NPV", "Equity Value", "per Share", "Safety"],
+                    vec![8, 12, 14, 11, 9],
+                );
+
+                for row in rows.iter() {
+                    table.add_row(vec![
+                        Cell::new(&row.ticker),
+                        Cell::new(money.format(row.npv)),
+                        Cell::new(money.format(row.equity_value)),
+                        Cell::new(match row.per_share {
+                            Some(per_share) => money.format(per_share),
+                            None => "".to_string(),
+                        }),
+                        Cell::new(match row.margin_of_safety {
+                            Some(margin) => format!("{:.1}%", margin * 100.0),
+                            // no value to discount the price from
+                            None if row.per_share.is_some_and(|value| value <= 0.0) => {
+                                "n/a".to_string()
+                            }
+                            None => "".to_string(),
+                        }),
+                    ]);
+                }
+
+                print_side_by_side(&[&table]);
+            }
+        }
+
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_row_per_company() {
+        let rows = BatchBuilder::new()
+            .add_company(
+                "AAA".to_string(),
+                IntrinsicBuilder::new()
+                    .add_current_value(10.0)
+                    .add_debt(Some(20.0)),
+            )
+            .add_company(
+                "BBB".to_string(),
+                IntrinsicBuilder::new().add_current_value(10.0),
+            )
+            .rows();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].equity_value, rows[1].equity_value - 20.0);
+        assert_eq!(rows[1].npv, rows[1].equity_value);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::utils::financial::cagr;
+use crate::utils::statistics::{mean, median, trimmed_mean};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Normalization {
+    Average,
+    Median,
+    TrimmedMean,
+    CagrTrend,
+}
+
+impl FromStr for Normalization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "average" => Ok(Normalization::Average),
+            "median" => Ok(Normalization::Median),
+            "trimmed-mean" => Ok(Normalization::TrimmedMean),
+            "cagr-trend" => Ok(Normalization::CagrTrend),
+            _ => Err(format!(
+                "unknown normalization `{}`, expected one of average, median, trimmed-mean, cagr-trend",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Normalization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Normalization::Average => "Average",
+            Normalization::Median => "Median",
+            Normalization::TrimmedMean => "Trimmed mean",
+            Normalization::CagrTrend => "CAGR trend",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct CashflowHistoryBuilder {
+    // cash flows ordered from the oldest to the latest year
+    pub history: Vec<f32>,
+    pub normalization: Normalization,
+}
+
+impl CashflowHistoryBuilder {
+    pub fn new() -> Self {
+        CashflowHistoryBuilder {
+            history: vec![],
+            normalization: Normalization::Average,
+        }
+    }
+
+    pub fn add_history(mut self, history: Vec<f32>) -> CashflowHistoryBuilder {
+        self.history = history;
+
+        self
+    }
+
+    pub fn add_normalization(mut self, normalization: Normalization) -> CashflowHistoryBuilder {
+        self.normalization = normalization;
+
+        self
+    }
+
+    /// Compounded growth between the oldest and the latest cash flow,
+    /// undefined when either end is not positive
+    pub fn cagr(&self) -> Option<f32> {
+        let first = *self.history.first()?;
+        let last = *self.history.last()?;
+        let periods = self.history.len() as i32 - 1;
+
+        if periods < 1 || first <= 0.0 || last <= 0.0 {
+            return None;
+        }
+
+        Some(cagr(last, first, periods))
+    }
+
+    /// Base cash flow to start the projections from
+    pub fn normalize(&self) -> Option<f32> {
+        if self.history.is_empty() {
+            return None;
+        }
+
+        match self.normalization {
+            Normalization::Average => Some(mean(&self.history)),
+            Normalization::Median => Some(median(&self.history)),
+            Normalization::TrimmedMean => Some(trimmed_mean(&self.history, 0.2)),
+            Normalization::CagrTrend => {
+                // trend line growing at the historical CAGR through the average,
+                // evaluated at the latest year
+                let growth = self.cagr()?;
+                let midpoint = (self.history.len() - 1) as f32 / 2.0;
+
+                Some(mean(&self.history) * (1.0 + growth).powf(midpoint))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_normalize_history_with_the_median() {
+        let history = CashflowHistoryBuilder::new()
+            .add_history(vec![8.1, 9.4, 7.2, 10.0, 9.8])
+            .add_normalization(Normalization::Median);
+
+        assert_eq!(history.normalize(), Some(9.4));
+    }
+
+    #[test]
+    fn should_not_project_a_trend_from_negative_cash_flows() {
+        let history = CashflowHistoryBuilder::new()
+            .add_history(vec![-1.0, 2.0, 3.0])
+            .add_normalization(Normalization::CagrTrend);
+
+        assert_eq!(history.cagr(), None);
+        assert_eq!(history.normalize(), None);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::utils::date::parse_date;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// SEC companyfacts JSON, as downloaded from the EDGAR XBRL API
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+pub struct CompanyFacts {
+    #[serde(rename = "entityName")]
+    pub entity_name: String,
+    // taxonomy (us-gaap, dei) -> concept -> facts
+    pub facts: HashMap<String, HashMap<String, Concept>>,
+}
+
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+pub struct Concept {
+    // unit (USD, shares) -> reported values
+    pub units: HashMap<String, Vec<Fact>>,
+}
+
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+pub struct Fact {
+    pub start: Option<String>,
+    pub end: String,
+    pub val: f64,
+    pub form: Option<String>,
+    pub filed: Option<String>,
+}
+
+/// Inputs of a valuation found in the filings, scaled to the unit of the analyst
+#[derive(PartialEq, Debug, Clone)]
+pub struct FactsDefaults {
+    // free cash flow from the oldest to the latest fiscal year
+    pub history: Vec<f32>,
+    pub cash: Option<f32>,
+    pub debt: Option<f32>,
+    pub shares_outstanding: Option<f32>,
+}
+
+const OPERATING_CASH_FLOW: [&str; 2] = [
+    "NetCashProvidedByUsedInOperatingActivities",
+    "NetCashProvidedByUsedInOperatingActivitiesContinuingOperations",
+];
+const CAPEX: [&str; 2] = [
+    "PaymentsToAcquirePropertyPlantAndEquipment",
+    "PaymentsToAcquireProductiveAssets",
+];
+const CASH: [&str; 2] = [
+    "CashAndCashEquivalentsAtCarryingValue",
+    "CashCashEquivalentsRestrictedCashAndRestrictedCashEquivalents",
+];
+
+impl CompanyFacts {
+    fn facts(&self, taxonomy: &str, concept: &str, unit: &str) -> Option<&Vec<Fact>> {
+        self.facts.get(taxonomy)?.get(concept)?.units.get(unit)
+    }
+
+    /// Fiscal year values reported in annual reports: (period end, value), oldest first
+    pub fn annual(&self, concepts: &[&str]) -> Vec<(String, f64)> {
+        let Some(facts) = concepts
+            .iter()
+            .find_map(|concept| self.facts("us-gaap", concept, "USD"))
+        else {
+            return vec![];
+        };
+
+        let mut by_end: HashMap<&str, &Fact> = HashMap::new();
+
+        for fact in facts.iter() {
+            let is_annual_report = fact
+                .form
+                .as_deref()
+                .is_some_and(|form| form.starts_with("10-K"));
+            let is_full_year = fact.start.as_deref().is_some_and(|start| {
+                match (parse_date(&fact.end), parse_date(start)) {
+                    (Ok(end), Ok(start)) => (330..=400).contains(&(end - start)),
+                    _ => false,
+                }
+            });
+
+            if !is_annual_report || !is_full_year {
+                continue;
+            }
+
+            // the latest filing restates the earlier ones
+            let previous = by_end.get(fact.end.as_str());
+            if previous.is_none_or(|previous| previous.filed <= fact.filed) {
+                by_end.insert(fact.end.as_str(), fact);
+            }
+        }
+
+        let mut values: Vec<(String, f64)> = by_end
+            .into_iter()
+            .map(|(end, fact)| (end.to_string(), fact.val))
+            .collect();
+        values.sort_by(|a, b| a.0.cmp(&b.0));
+
+        values
+    }
+
+    /// Value at the latest balance sheet date of the first concept reported
+    pub fn latest(&self, taxonomy: &str, concepts: &[&str], unit: &str) -> Option<f64> {
+        concepts
+            .iter()
+            .find_map(|concept| self.facts(taxonomy, concept, unit))?
+            .iter()
+            .max_by(|a, b| (&a.end, &a.filed).cmp(&(&b.end, &b.filed)))
+            .map(|fact| fact.val)
+    }
+
+    /// Operating cash flow minus capex of each fiscal year reporting both
+    pub fn free_cash_flow(&self) -> Vec<(String, f64)> {
+        let capex: HashMap<String, f64> = self.annual(&CAPEX).into_iter().collect();
+
+        self.annual(&OPERATING_CASH_FLOW)
+            .into_iter()
+            .filter_map(|(end, cash_flow)| {
+                capex
+                    .get(&end)
+                    .map(|capex| (end.clone(), cash_flow - capex))
+            })
+            .collect()
+    }
+
+    /// Total debt, the current portion is added when only the noncurrent one is reported
+    pub fn debt(&self) -> Option<f64> {
+        self.latest("us-gaap", &["LongTermDebt"], "USD")
+            .or_else(|| {
+                let noncurrent = self.latest("us-gaap", &["LongTermDebtNoncurrent"], "USD")?;
+                let current = self
+                    .latest("us-gaap", &["LongTermDebtCurrent", "DebtCurrent"], "USD")
+                    .unwrap_or(0.0);
+
+                Some(noncurrent + current)
+            })
+    }
+
+    pub fn shares_outstanding(&self) -> Option<f64> {
+        self.latest("dei", &["EntityCommonStockSharesOutstanding"], "shares")
+            .or_else(|| self.latest("us-gaap", &["CommonStockSharesOutstanding"], "shares"))
+    }
+
+    /// Last `years` of free cash flow history and the latest balance sheet, divided by `scale`
+    pub fn defaults(&self, years: usize, scale: f64) -> FactsDefaults {
+        let history = self.free_cash_flow();
+        let skip = history.len().saturating_sub(years);
+
+        FactsDefaults {
+            history: history
+                .into_iter()
+                .skip(skip)
+                .map(|(_, value)| (value / scale) as f32)
+                .collect(),
+            cash: self
+                .latest("us-gaap", &CASH, "USD")
+                .map(|cash| (cash / scale) as f32),
+            debt: self.debt().map(|debt| (debt / scale) as f32),
+            shares_outstanding: self
+                .shares_outstanding()
+                .map(|shares| (shares / scale) as f32),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FACTS: &str = r#"{
+        "cik": 1,
+        "entityName": "ACME CORP",
+        "facts": {
+            "dei": {
+                "EntityCommonStockSharesOutstanding": {"units": {"shares": [
+                    {"end": "2024-07-20", "val": 2000000000, "form": "10-K", "filed": "2024-07-30"}
+                ]}}
+            },
+            "us-gaap": {
+                "NetCashProvidedByUsedInOperatingActivities": {"units": {"USD": [
+                    {"start": "2022-07-01", "end": "2023-06-30", "val": 9000000000, "form": "10-K", "filed": "2023-07-27"},
+                    {"start": "2022-07-01", "end": "2023-06-30", "val": 9500000000, "form": "10-K", "filed": "2024-07-30"},
+                    {"start": "2023-07-01", "end": "2024-06-30", "val": 12000000000, "form": "10-K", "filed": "2024-07-30"},
+                    {"start": "2024-01-01", "end": "2024-03-31", "val": 3000000000, "form": "10-Q", "filed": "2024-04-25"}
+                ]}},
+                "PaymentsToAcquirePropertyPlantAndEquipment": {"units": {"USD": [
+                    {"start": "2022-07-01", "end": "2023-06-30", "val": 2500000000, "form": "10-K", "filed": "2024-07-30"},
+                    {"start": "2023-07-01", "end": "2024-06-30", "val": 3000000000, "form": "10-K", "filed": "2024-07-30"}
+                ]}},
+                "CashAndCashEquivalentsAtCarryingValue": {"units": {"USD": [
+                    {"end": "2023-06-30", "val": 4000000000, "form": "10-K", "filed": "2023-07-27"},
+                    {"end": "2024-06-30", "val": 5000000000, "form": "10-K", "filed": "2024-07-30"}
+                ]}},
+                "LongTermDebtNoncurrent": {"units": {"USD": [
+                    {"end": "2024-06-30", "val": 6000000000, "form": "10-K", "filed": "2024-07-30"}
+                ]}},
+                "LongTermDebtCurrent": {"units": {"USD": [
+                    {"end": "2024-06-30", "val": 1000000000, "form": "10-K", "filed": "2024-07-30"}
+                ]}}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_free_cash_flow_uses_the_latest_annual_filing() {
+        let facts: CompanyFacts = serde_json::from_str(FACTS).unwrap();
+
+        assert_eq!(
+            facts.free_cash_flow(),
+            vec![
+                ("2023-06-30".to_string(), 7000000000.0),
+                ("2024-06-30".to_string(), 9000000000.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_defaults_are_scaled_to_billions() {
+        let facts: CompanyFacts = serde_json::from_str(FACTS).unwrap();
+
+        assert_eq!(
+            facts.defaults(1, 1e9),
+            FactsDefaults {
+                history: vec![9.0],
+                cash: Some(5.0),
+                debt: Some(7.0),
+                shares_outstanding: Some(2.0),
+            }
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use crate::utils::financial::margin_of_safety;
+use crate::utils::statistics::{median, quantile};
+use crate::utils::table::{new_table, print_side_by_side};
+use comfy_table::Cell;
+use serde::Deserialize;
+
+/// Comparable company, all the amounts are per share
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+pub struct Peer {
+    pub ticker: String,
+    pub price: f32,
+    pub eps: f32,
+    pub ebitda: f32,
+    pub fcf: f32,
+    pub book_value: f32,
+    pub net_debt: f32,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Multiple {
+    PriceEarnings,
+    EvEbitda,
+    PriceFcf,
+    PriceBook,
+}
+
+impl Multiple {
+    pub fn all() -> [Multiple; 4] {
+        [
+            Multiple::PriceEarnings,
+            Multiple::EvEbitda,
+            Multiple::PriceFcf,
+            Multiple::PriceBook,
+        ]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Multiple::PriceEarnings => "P/E",
+            Multiple::EvEbitda => "EV/EBITDA",
+            Multiple::PriceFcf => "P/FCF",
+            Multiple::PriceBook => "P/B",
+        }
+    }
+}
+
+impl Peer {
+    /// Multiple of the peer, none when the denominator is not positive
+    pub fn multiple(&self, multiple: Multiple) -> Option<f32> {
+        let (numerator, denominator) = match multiple {
+            Multiple::PriceEarnings => (self.price, self.eps),
+            Multiple::EvEbitda => (self.price + self.net_debt, self.ebitda),
+            Multiple::PriceFcf => (self.price, self.fcf),
+            Multiple::PriceBook => (self.price, self.book_value),
+        };
+
+        if denominator <= 0.0 {
+            return None;
+        }
+
+        Some(numerator / denominator)
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct CompsBuilder {
+    peers: Vec<Peer>,
+    // per share figures of the company being valued
+    eps: Option<f32>,
+    ebitda: Option<f32>,
+    fcf: Option<f32>,
+    book_value: Option<f32>,
+    net_debt: Option<f32>,
+    // optional value per share from a DCF
+    dcf_value: Option<f32>,
+    // optional market price of one share
+    share_price: Option<f32>,
+}
+
+impl CompsBuilder {
+    pub fn new() -> CompsBuilder {
+        CompsBuilder {
+            peers: vec![],
+            eps: None,
+            ebitda: None,
+            fcf: None,
+            book_value: None,
+            net_debt: None,
+            dcf_value: None,
+            share_price: None,
+        }
+    }
+
+    pub fn add_peers(mut self, peers: Vec<Peer>) -> CompsBuilder {
+        self.peers = peers;
+
+        self
+    }
+
+    pub fn add_eps(mut self, eps: Option<f32>) -> CompsBuilder {
+        self.eps = eps;
+
+        self
+    }
+
+    pub fn add_ebitda(mut self, ebitda: Option<f32>) -> CompsBuilder {
+        self.ebitda = ebitda;
+
+        self
+    }
+
+    pub fn add_fcf(mut self, fcf: Option<f32>) -> CompsBuilder {
+        self.fcf = fcf;
+
+        self
+    }
+
+    pub fn add_book_value(mut self, book_value: Option<f32>) -> CompsBuilder {
+        self.book_value = book_value;
+
+        self
+    }
+
+    pub fn add_net_debt(mut self, net_debt: Option<f32>) -> CompsBuilder {
+        self.net_debt = net_debt;
+
+        self
+    }
+
+    pub fn add_dcf_value(mut self, dcf_value: Option<f32>) -> CompsBuilder {
+        self.dcf_value = dcf_value;
+
+        self
+    }
+
+    pub fn add_share_price(mut self, share_price: Option<f32>) -> CompsBuilder {
+        self.share_price = share_price;
+
+        self
+    }
+
+    /// (first quartile, median, third quartile) of the multiple across peers
+    pub fn quartiles(&self, multiple: Multiple) -> Option<(f32, f32, f32)> {
+        let values: Vec<f32> = self
+            .peers
+            .iter()
+            .filter_map(|peer| peer.multiple(multiple))
+            .collect();
+
+        if values.is_empty() {
+            return None;
+        }
+
+        Some((
+            quantile(&values, 0.25),
+            median(&values),
+            quantile(&values, 0.75),
+        ))
+    }
+
+    /// Price per share of the target implied by a peer multiple
+    pub fn implied_price(&self, multiple: Multiple, value: f32) -> Option<f32> {
+        match multiple {
+            Multiple::PriceEarnings => self.eps.map(|eps| eps * value),
+            Multiple::EvEbitda => self
+                .ebitda
+                .map(|ebitda| ebitda * value - self.net_debt.unwrap_or(0.0)),
+            Multiple::PriceFcf => self.fcf.map(|fcf| fcf * value),
+            Multiple::PriceBook => self.book_value.map(|book_value| book_value * value),
+        }
+    }
+
+    pub fn execute(self) -> f32 {
+        // Table 1: PEERS
+        let mut peers_table = new_table(
+            vec!["Peer", "P/E", "EV/EBITDA", "P/FCF", "P/B"],
+            vec![7, 7, 11, 7, 7],
+        );
+
+        for peer in self.peers.iter() {
+            let mut row = vec![Cell::new(&peer.ticker)];
+            for multiple in Multiple::all() {
+                row.push(Cell::new(match peer.multiple(multiple) {
+                    Some(value) => format!("{:.1}", value),
+                    None => "n/a".to_string(),
+                }));
+            }
+            peers_table.add_row(row);
+        }
+
+        // Table 2: PEER MULTIPLES AND IMPLIED VALUE PER SHARE
+        let mut multiples_table = new_table(
+            vec!["Multiple", "Q1", "Median", "Q3", "Low", "Mid", "High"],
+            vec![11, 8, 8, 8, 9, 9, 9],
+        );
+
+        let mut lows = vec![];
+        let mut mids = vec![];
+        let mut highs = vec![];
+
+        for multiple in Multiple::all() {
+            let Some((q1, q2, q3)) = self.quartiles(multiple) else {
+                continue;
+            };
+
+            let mut row = vec![
+                Cell::new(multiple.name()),
+                Cell::new(format!("{:.1}x", q1)),
+                Cell::new(format!("{:.1}x", q2)),
+                Cell::new(format!("{:.1}x", q3)),
+            ];
+
+            if let (Some(low), Some(mid), Some(high)) = (
+                self.implied_price(multiple, q1),
+                self.implied_price(multiple, q2),
+                self.implied_price(multiple, q3),
+            ) {
+                row.push(Cell::new(format!("${:.2}", low)));
+                row.push(Cell::new(format!("${:.2}", mid)));
+                row.push(Cell::new(format!("${:.2}", high)));
+                lows.push(low);
+                mids.push(mid);
+                highs.push(high);
+            }
+
+            multiples_table.add_row(row);
+        }
+
+        if mids.is_empty() {
+            print_side_by_side(&[&peers_table, &multiples_table]);
+            println!("Provide at least one of eps, ebitda, fcf or book value for the target.");
+
+            return 0.0;
+        }
+
+        let result = median(&mids);
+        let low = lows.iter().cloned().fold(f32::INFINITY, f32::min);
+        let high = highs.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+
+        multiples_table.add_row(vec![Cell::new("")]);
+        multiples_table.add_row(vec![
+            Cell::new("Range"),
+            Cell::new(""),
+            Cell::new(""),
+            Cell::new(""),
+            Cell::new(format!("${:.2}", low)),
+            Cell::new(format!("${:.2}", result)),
+            Cell::new(format!("${:.2}", high)),
+        ]);
+
+        if let Some(dcf_value) = self.dcf_value {
+            multiples_table.add_row(vec![
+                Cell::new("DCF"),
+                Cell::new(""),
+                Cell::new(""),
+                Cell::new(""),
+                Cell::new(""),
+                Cell::new(format!("${:.2}", dcf_value)),
+                Cell::new(format!("{:+.1}%", (dcf_value / result - 1.0) * 100.0)),
+            ]);
+        }
+
+        if let Some(price) = self.share_price {
+            multiples_table.add_row(vec![
+                Cell::new("Price"),
+                Cell::new(""),
+                Cell::new(""),
+                Cell::new(""),
+                Cell::new(""),
+                Cell::new(format!("${:.2}", price)),
+            ]);
+            multiples_table.add_row(vec![
+                Cell::new("Safety"),
+                Cell::new(""),
+                Cell::new(""),
+                Cell::new(""),
+                Cell::new(""),
+                Cell::new(match margin_of_safety(result, price) {
+                    Some(margin) => format!("{:.1}%", margin * 100.0),
+                    None => "n/a".to_string(),
+                }),
+            ]);
+        }
+
+        print_side_by_side(&[&peers_table, &multiples_table]);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(ticker: &str, price: f32, eps: f32) -> Peer {
+        Peer {
+            ticker: ticker.to_string(),
+            price,
+            eps,
+            ebitda: 0.0,
+            fcf: 0.0,
+            book_value: 0.0,
+            net_debt: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_quartiles_skip_peers_with_negative_earnings() {
+        let comps = CompsBuilder::new().add_peers(vec![
+            peer("A", 100.0, 10.0),
+            peer("B", 100.0, 5.0),
+            peer("C", 100.0, 4.0),
+            peer("D", 100.0, -2.0),
+        ]);
+
+        assert_eq!(
+            comps.quartiles(Multiple::PriceEarnings),
+            Some((15.0, 20.0, 22.5))
+        );
+        assert_eq!(comps.quartiles(Multiple::PriceBook), None);
+    }
+
+    #[test]
+    fn test_value_is_the_median_of_the_implied_prices() {
+        let comps = CompsBuilder::new()
+            .add_peers(vec![peer("A", 100.0, 10.0), peer("B", 100.0, 5.0)])
+            .add_eps(Some(2.0));
+
+        assert_eq!(comps.execute(), 30.0);
+    }
+}
@@ -0,0 +1,246 @@
+use super::growth_assumption_builder::GrowthAssumptionBuilder;
+use crate::utils::config::DEFAULT_DISCOUNT_RATE;
+use crate::utils::financial::{margin_of_safety, pv};
+use crate::utils::table::{new_table, print_side_by_side};
+use comfy_table::Cell;
+
+#[derive(PartialEq, Debug)]
+pub struct DdmBuilder {
+    // dividend per share paid over the last year
+    dividend: Option<f32>,
+    // cost of equity
+    rate: Option<f32>,
+    // optional dividend growth stages, none for the single-stage gordon model
+    growth_assumptions: GrowthAssumptionBuilder,
+    // optional perpetual growth after the stages, defaults to the last stage growth
+    terminal_growth: Option<f32>,
+    // optional market price of one share
+    share_price: Option<f32>,
+}
+
+impl DdmBuilder {
+    pub fn new() -> DdmBuilder {
+        DdmBuilder {
+            dividend: None,
+            rate: Some(DEFAULT_DISCOUNT_RATE),
+            growth_assumptions: GrowthAssumptionBuilder::new(),
+            terminal_growth: None,
+            share_price: None,
+        }
+    }
+
+    pub fn add_dividend(mut self, dividend: f32) -> DdmBuilder {
+        self.dividend = Some(dividend);
+
+        self
+    }
+
+    pub fn add_rate(mut self, rate: f32) -> DdmBuilder {
+        self.rate = Some(rate);
+
+        self
+    }
+
+    pub fn add_growth_assumptions(mut self, growths: GrowthAssumptionBuilder) -> DdmBuilder {
+        self.growth_assumptions = growths;
+
+        self
+    }
+
+    pub fn add_terminal_growth(mut self, terminal_growth: Option<f32>) -> DdmBuilder {
+        self.terminal_growth = terminal_growth;
+
+        self
+    }
+
+    pub fn add_share_price(mut self, share_price: Option<f32>) -> DdmBuilder {
+        self.share_price = share_price;
+
+        self
+    }
+
+    /// Perpetual growth after the stages, defaults to the last stage growth
+    fn terminal_growth(&self) -> Option<f32> {
+        self.terminal_growth
+            .or_else(|| self.growth_assumptions.assumptions.last().copied())
+    }
+
+    /// Checks the gordon growth model applies, the terminal growth has to stay below the cost of equity
+    pub fn validate(&self) -> Result<(), String> {
+        let rate = self.rate.unwrap();
+        let terminal_growth = self
+            .terminal_growth()
+            .ok_or("a terminal growth or at least one growth stage is required")?;
+
+        if rate <= terminal_growth {
+            return Err(format!(
+                "the terminal growth of {:.1}% must be below the cost of equity of {:.1}%",
+                terminal_growth * 100.0,
+                rate * 100.0
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn execute(self) -> f32 {
+        let mut dividend = self.dividend.unwrap();
+        let rate = self.rate.unwrap();
+        let growth_assumptions = &self.growth_assumptions.assumptions;
+        let terminal_growth = self
+            .terminal_growth()
+            .expect("a terminal growth or at least one growth stage is required");
+
+        // Table 1: ASSUMPTIONS
+        let mut assumptions_table = new_table(vec!["Assumptions", "Value"], vec![16, 16]);
+
+        assumptions_table.add_row(vec![
+            Cell::new("Dividend (D0)"),
+            Cell::new(format!("${:.2}", dividend)),
+        ]);
+        assumptions_table.add_row(vec![
+            Cell::new("Cost of Equity"),
+            Cell::new(format!("{:.1}%", rate * 100.0)),
+        ]);
+
+        if !growth_assumptions.is_empty() {
+            let growth_years: Vec<String> = growth_assumptions
+                .iter()
+                .enumerate()
+                .map(|(i, &g)| format!("Year {}: {:.1}%", i + 1, g * 100.0))
+                .collect();
+            assumptions_table.add_row(vec![
+                Cell::new("Dividend Growth"),
+                Cell::new(growth_years.join("\n")),
+            ]);
+        }
+
+        assumptions_table.add_row(vec![
+            Cell::new("Terminal Growth"),
+            Cell::new(format!("{:.1}%", terminal_growth * 100.0)),
+        ]);
+
+        if let Some(price) = self.share_price {
+            assumptions_table.add_row(vec![
+                Cell::new("Share Price"),
+                Cell::new(format!("${:.2}", price)),
+            ]);
+        }
+
+        // Table 2: DIVIDEND PROJECTIONS
+        let mut dividends_table = new_table(vec!["Year", "Dividend", "PV"], vec![10, 10, 10]);
+
+        let mut year = 0;
+        let mut dividends_pv = 0.0;
+        dividends_table.add_row(vec![
+            Cell::new(year),
+            Cell::new(format!("{:.2}", dividend)),
+            Cell::new(""),
+        ]);
+
+        for assumption_rate in growth_assumptions.iter() {
+            year += 1;
+            dividend *= 1. + assumption_rate;
+            let pv_value = pv(rate, year, dividend);
+
+            dividends_table.add_row(vec![
+                Cell::new(year),
+                Cell::new(format!("{:.2}", dividend)),
+                Cell::new(format!("{:.2}", pv_value)),
+            ]);
+            dividends_pv += pv_value;
+        }
+
+        // gordon growth on the next year dividend
+        let terminal_value = dividend * (1. + terminal_growth) / (rate - terminal_growth);
+        let terminal_pv = pv(rate, year, terminal_value);
+
+        dividends_table.add_row(vec![
+            Cell::new("TV"),
+            Cell::new(format!("{:.2}", terminal_value)),
+            Cell::new(format!("{:.2}", terminal_pv)),
+        ]);
+
+        let result = dividends_pv + terminal_pv;
+
+        // Table 3: VALUATION
+        let mut valuation_table = new_table(vec!["Valuation", "Value"], vec![16, 16]);
+
+        if !growth_assumptions.is_empty() {
+            valuation_table.add_row(vec![
+                Cell::new("PV Dividends"),
+                Cell::new(format!("${:.2}", dividends_pv)),
+            ]);
+        }
+
+        valuation_table.add_row(vec![
+            Cell::new("+ PV Terminal"),
+            Cell::new(format!("${:.2}", terminal_pv)),
+        ]);
+        valuation_table.add_row(vec![
+            Cell::new("= per Share"),
+            Cell::new(format!("${:.2}", result)),
+        ]);
+
+        if let Some(price) = self.share_price {
+            valuation_table.add_row(vec![Cell::new("")]);
+            valuation_table.add_row(vec![
+                Cell::new("Price"),
+                Cell::new(format!("${:.2}", price)),
+            ]);
+            valuation_table.add_row(vec![
+                Cell::new("Margin of Safety"),
+                Cell::new(match margin_of_safety(result, price) {
+                    Some(margin) => format!("{:.1}%", margin * 100.0),
+                    None => "n/a".to_string(),
+                }),
+            ]);
+        }
+
+        print_side_by_side(&[&assumptions_table, &dividends_table, &valuation_table]);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::growth_assumption_builder::GrowthAssumption;
+
+    #[test]
+    fn test_single_stage_gordon_growth() {
+        let ddm = DdmBuilder::new()
+            .add_dividend(2.0)
+            .add_rate(0.08)
+            .add_terminal_growth(Some(0.03));
+
+        assert_eq!(ddm.execute(), 41.2);
+    }
+
+    #[test]
+    fn test_terminal_growth_above_the_cost_of_equity_is_rejected() {
+        let ddm = DdmBuilder::new()
+            .add_dividend(2.0)
+            .add_rate(0.05)
+            .add_terminal_growth(Some(0.06));
+
+        assert_eq!(
+            ddm.validate(),
+            Err("the terminal growth of 6.0% must be below the cost of equity of 5.0%".to_string())
+        );
+    }
+
+    #[test]
+    fn test_multi_stage_discounts_each_dividend_and_the_terminal_value() {
+        let ddm = DdmBuilder::new()
+            .add_dividend(2.0)
+            .add_rate(0.08)
+            .add_growth_assumptions(
+                GrowthAssumptionBuilder::new().add(GrowthAssumption(3, 0.10, None)),
+            )
+            .add_terminal_growth(Some(0.03));
+
+        assert_eq!(ddm.execute(), 49.75652);
+    }
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Options or warrants outstanding: (count, strike price)
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct StockOption(pub f32, pub f32);
+
+impl StockOption {
+    /// Net new shares under the treasury stock method, the exercise proceeds
+    /// are assumed to buy back shares at `price`
+    pub fn treasury_shares(&self, price: f32) -> f32 {
+        let StockOption(count, strike) = *self;
+
+        if price <= strike {
+            return 0.0;
+        }
+
+        count - count * strike / price
+    }
+}
+
+/// Two numbers in the format `first,second`, the second being a price above zero
+fn parse_pair(s: &str, format: &str) -> Result<(f32, f32), String> {
+    let (first, second) = s
+        .split_once(',')
+        .ok_or_else(|| format!("expected {}, got `{}`", format, s))?;
+    let number = |token: &str| {
+        token.trim().parse::<f32>().map_err(|_| {
+            format!(
+                "`{}` is not a number in `{}`, expected {}",
+                token.trim(),
+                s,
+                format
+            )
+        })
+    };
+    let (first, second) = (number(first)?, number(second)?);
+
+    if second <= 0.0 {
+        return Err(format!("the price of `{}` must be above zero", s));
+    }
+
+    Ok((first, second))
+}
+
+impl FromStr for StockOption {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_pair(s, "count,strike").map(|(count, strike)| StockOption(count, strike))
+    }
+}
+
+/// Convertible debt: (face amount, conversion price)
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Convertible(pub f32, pub f32);
+
+impl Convertible {
+    /// Holders convert when the share is worth more than the conversion price
+    pub fn is_converted(&self, price: f32) -> bool {
+        price > self.1
+    }
+
+    pub fn shares(&self) -> f32 {
+        self.0 / self.1
+    }
+}
+
+impl FromStr for Convertible {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_pair(s, "amount,conversion_price")
+            .map(|(amount, conversion_price)| Convertible(amount, conversion_price))
+    }
+}
+
+/// Share count after `years` of buybacks (negative rate) or dilution
+pub fn shares_at(shares: f32, annual_change: f32, years: i32) -> f32 {
+    shares * (1.0 + annual_change).powi(years)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_treasury_stock_method_only_counts_options_in_the_money() {
+        assert_eq!(StockOption(10.0, 25.0).treasury_shares(50.0), 5.0);
+        assert_eq!(StockOption(10.0, 25.0).treasury_shares(20.0), 0.0);
+    }
+
+    #[test]
+    fn test_convertible_converts_above_the_conversion_price() {
+        let convertible = Convertible(100.0, 40.0);
+
+        assert!(convertible.is_converted(45.0));
+        assert!(!convertible.is_converted(35.0));
+        assert_eq!(convertible.shares(), 2.5);
+    }
+
+    #[test]
+    fn test_parse_options_and_convertibles() {
+        assert_eq!("10,25".parse(), Ok(StockOption(10.0, 25.0)));
+        assert_eq!("100, 40".parse(), Ok(Convertible(100.0, 40.0)));
+        assert_eq!(
+            "10".parse::<StockOption>(),
+            Err("expected count,strike, got `10`".to_string())
+        );
+        assert!("100,0".parse::<Convertible>().is_err());
+    }
+}
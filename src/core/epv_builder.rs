@@ -0,0 +1,319 @@
+use crate::utils::config::DEFAULT_DISCOUNT_RATE;
+use crate::utils::table::{new_table, print_side_by_side};
+use comfy_table::Cell;
+
+#[derive(PartialEq, Debug)]
+pub struct EpvBuilder {
+    // normalized operating earnings before interest and taxes
+    ebit: Option<f32>,
+    tax_rate: Option<f32>,
+    // cost of capital
+    rate: Option<f32>,
+    // optional depreciation and amortization
+    depreciation: Option<f32>,
+    // optional capex needed to sustain the current earnings
+    maintenance_capex: Option<f32>,
+    // optional cash amount
+    cash: Option<f32>,
+    // optional debt amount
+    debt: Option<f32>,
+    // optional shares outstanding
+    shares_outstanding: Option<f32>,
+    // optional cost of reproducing the assets of the business
+    reproduction_value: Option<f32>,
+    // optional equity value including growth, as computed by a DCF
+    growth_value: Option<f32>,
+}
+
+impl EpvBuilder {
+    pub fn new() -> EpvBuilder {
+        EpvBuilder {
+            ebit: None,
+            tax_rate: Some(0.0),
+            rate: Some(DEFAULT_DISCOUNT_RATE),
+            depreciation: None,
+            maintenance_capex: None,
+            cash: None,
+            debt: None,
+            shares_outstanding: None,
+            reproduction_value: None,
+            growth_value: None,
+        }
+    }
+
+    pub fn add_ebit(mut self, ebit: f32) -> EpvBuilder {
+        self.ebit = Some(ebit);
+
+        self
+    }
+
+    pub fn add_tax_rate(mut self, tax_rate: f32) -> EpvBuilder {
+        self.tax_rate = Some(tax_rate);
+
+        self
+    }
+
+    pub fn add_rate(mut self, rate: f32) -> EpvBuilder {
+        self.rate = Some(rate);
+
+        self
+    }
+
+    pub fn add_depreciation(mut self, depreciation: Option<f32>) -> EpvBuilder {
+        self.depreciation = depreciation;
+
+        self
+    }
+
+    pub fn add_maintenance_capex(mut self, maintenance_capex: Option<f32>) -> EpvBuilder {
+        self.maintenance_capex = maintenance_capex;
+
+        self
+    }
+
+    pub fn add_cash(mut self, cash: Option<f32>) -> EpvBuilder {
+        self.cash = cash;
+
+        self
+    }
+
+    pub fn add_debt(mut self, debt: Option<f32>) -> EpvBuilder {
+        self.debt = debt;
+
+        self
+    }
+
+    pub fn add_shares_outstanding(mut self, shares_outstanding: Option<f32>) -> EpvBuilder {
+        self.shares_outstanding = shares_outstanding;
+
+        self
+    }
+
+    pub fn add_reproduction_value(mut self, reproduction_value: Option<f32>) -> EpvBuilder {
+        self.reproduction_value = reproduction_value;
+
+        self
+    }
+
+    pub fn add_growth_value(mut self, growth_value: Option<f32>) -> EpvBuilder {
+        self.growth_value = growth_value;
+
+        self
+    }
+
+    /// Depreciation above the capex needed to maintain the business
+    pub fn excess_depreciation(&self) -> f32 {
+        match (self.depreciation, self.maintenance_capex) {
+            (Some(depreciation), Some(capex)) => depreciation - capex,
+            _ => 0.0,
+        }
+    }
+
+    /// Sustainable after-tax earnings with no growth
+    pub fn adjusted_earnings(&self) -> f32 {
+        (self.ebit.unwrap() + self.excess_depreciation()) * (1.0 - self.tax_rate.unwrap())
+    }
+
+    pub fn execute(self) -> f32 {
+        let ebit = self.ebit.unwrap();
+        let tax_rate = self.tax_rate.unwrap();
+        let rate = self.rate.unwrap();
+        let adjusted_earnings = self.adjusted_earnings();
+        let enterprise_epv = adjusted_earnings / rate;
+
+        // Table 1: ASSUMPTIONS
+        let mut assumptions_table = new_table(vec!["Assumptions", "Value"], vec![20, 14]);
+
+        assumptions_table.add_row(vec![
+            Cell::new("Normalized EBIT"),
+            Cell::new(format!("${:.2}", ebit)),
+        ]);
+        assumptions_table.add_row(vec![
+            Cell::new("Tax Rate"),
+            Cell::new(format!("{:.1}%", tax_rate * 100.0)),
+        ]);
+        assumptions_table.add_row(vec![
+            Cell::new("Cost of Capital"),
+            Cell::new(format!("{:.1}%", rate * 100.0)),
+        ]);
+
+        if let Some(depreciation) = self.depreciation {
+            assumptions_table.add_row(vec![
+                Cell::new("Depreciation"),
+                Cell::new(format!("${:.2}", depreciation)),
+            ]);
+        }
+
+        if let Some(capex) = self.maintenance_capex {
+            assumptions_table.add_row(vec![
+                Cell::new("Maintenance Capex"),
+                Cell::new(format!("${:.2}", capex)),
+            ]);
+        }
+
+        if let Some(cash_amt) = self.cash {
+            assumptions_table.add_row(vec![
+                Cell::new("Cash"),
+                Cell::new(format!("${:.2}", cash_amt)),
+            ]);
+        }
+
+        if let Some(debt_amt) = self.debt {
+            assumptions_table.add_row(vec![
+                Cell::new("Debt"),
+                Cell::new(format!("${:.2}", debt_amt)),
+            ]);
+        }
+
+        if let Some(shares) = self.shares_outstanding {
+            assumptions_table.add_row(vec![
+                Cell::new("Shares Outstanding"),
+                Cell::new(format!("{:.1}", shares)),
+            ]);
+        }
+
+        // Table 2: EARNINGS POWER
+        let mut earnings_table = new_table(vec!["Earnings Power", "Value"], vec![20, 14]);
+
+        earnings_table.add_row(vec![Cell::new("EBIT"), Cell::new(format!("${:.2}", ebit))]);
+
+        if self.depreciation.is_some() && self.maintenance_capex.is_some() {
+            earnings_table.add_row(vec![
+                Cell::new("+ Excess D&A"),
+                Cell::new(format!("${:.2}", self.excess_depreciation())),
+            ]);
+        }
+
+        earnings_table.add_row(vec![
+            Cell::new("× (1 - Tax)"),
+            Cell::new(format!("{:.1}%", (1.0 - tax_rate) * 100.0)),
+        ]);
+        earnings_table.add_row(vec![
+            Cell::new("= Adj. Earnings"),
+            Cell::new(format!("${:.2}", adjusted_earnings)),
+        ]);
+        earnings_table.add_row(vec![
+            Cell::new("÷ Cost of Capital"),
+            Cell::new(format!("{:.1}%", rate * 100.0)),
+        ]);
+        earnings_table.add_row(vec![
+            Cell::new("= EPV"),
+            Cell::new(format!("${:.2}", enterprise_epv)),
+        ]);
+
+        let mut result = enterprise_epv;
+
+        if let Some(debt_amt) = self.debt {
+            earnings_table.add_row(vec![
+                Cell::new("- Debt"),
+                Cell::new(format!("${:.2}", debt_amt)),
+            ]);
+            result -= debt_amt;
+        }
+
+        if let Some(cash_amt) = self.cash {
+            earnings_table.add_row(vec![
+                Cell::new("+ Cash"),
+                Cell::new(format!("${:.2}", cash_amt)),
+            ]);
+            result += cash_amt;
+        }
+
+        earnings_table.add_row(vec![
+            Cell::new("= Equity EPV"),
+            Cell::new(format!("${:.2}", result)),
+        ]);
+
+        if let Some(shares) = self.shares_outstanding {
+            earnings_table.add_row(vec![Cell::new("")]);
+            earnings_table.add_row(vec![
+                Cell::new("÷ Shares"),
+                Cell::new(format!("{:.1}", shares)),
+            ]);
+            earnings_table.add_row(vec![
+                Cell::new("= per Share"),
+                Cell::new(format!("${:.2}", result / shares)),
+            ]);
+        }
+
+        // Table 3: COMPARISON
+        let mut comparison_table = new_table(vec!["Comparison", "Value"], vec![20, 14]);
+
+        if let Some(reproduction_value) = self.reproduction_value {
+            comparison_table.add_row(vec![
+                Cell::new("Reproduction"),
+                Cell::new(format!("${:.2}", reproduction_value)),
+            ]);
+            comparison_table.add_row(vec![
+                Cell::new("EPV"),
+                Cell::new(format!("${:.2}", enterprise_epv)),
+            ]);
+            // a positive franchise value means the business earns above its asset base
+            comparison_table.add_row(vec![
+                Cell::new("= Franchise"),
+                Cell::new(format!("${:.2}", enterprise_epv - reproduction_value)),
+            ]);
+        }
+
+        if let Some(growth_value) = self.growth_value {
+            if self.reproduction_value.is_some() {
+                comparison_table.add_row(vec![Cell::new("")]);
+            }
+            comparison_table.add_row(vec![
+                Cell::new("DCF Equity"),
+                Cell::new(format!("${:.2}", growth_value)),
+            ]);
+            comparison_table.add_row(vec![
+                Cell::new("- Equity EPV"),
+                Cell::new(format!("${:.2}", result)),
+            ]);
+            comparison_table.add_row(vec![
+                Cell::new("= Growth"),
+                Cell::new(format!("${:.2}", growth_value - result)),
+            ]);
+            comparison_table.add_row(vec![
+                Cell::new("Paid for Growth"),
+                Cell::new(format!(
+                    "{:.1}%",
+                    (growth_value - result) / growth_value * 100.0
+                )),
+            ]);
+        }
+
+        if self.reproduction_value.is_some() || self.growth_value.is_some() {
+            print_side_by_side(&[&assumptions_table, &earnings_table, &comparison_table]);
+        } else {
+            print_side_by_side(&[&assumptions_table, &earnings_table]);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adjusted_earnings_add_back_excess_depreciation() {
+        let epv = EpvBuilder::new()
+            .add_ebit(10.0)
+            .add_tax_rate(0.25)
+            .add_depreciation(Some(4.0))
+            .add_maintenance_capex(Some(2.0));
+
+        assert_eq!(epv.adjusted_earnings(), 9.0);
+    }
+
+    #[test]
+    fn test_equity_earnings_power_value() {
+        let epv = EpvBuilder::new()
+            .add_ebit(10.0)
+            .add_tax_rate(0.25)
+            .add_rate(0.10)
+            .add_cash(Some(5.0))
+            .add_debt(Some(20.0));
+
+        assert_eq!(epv.execute(), 60.0);
+    }
+}
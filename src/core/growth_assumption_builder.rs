@@ -1,3 +1,8 @@
+use crate::utils::grammar::{parse_rate, parse_years};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub struct GrowthAssumption(pub u8, pub f32, pub Option<f32>);
 
 impl GrowthAssumption {
@@ -15,7 +20,74 @@ impl GrowthAssumption {
         result
     }
 }
-#[derive(PartialEq, Debug, Clone)]
+
+/// Growth stage in one of the forms `5y @ 12% -1%/y`, `years=5,rate=12%,step=-1%` or `5,0.12,-0.01`
+impl FromStr for GrowthAssumption {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (years, rate, step) = if let Some((years, rates)) = s.split_once('@') {
+            let mut tokens = rates.split_whitespace();
+            let rate = tokens
+                .next()
+                .ok_or_else(|| format!("missing growth rate after `@` in `{}`", s))?;
+            let step = tokens.next().map(|step| {
+                ["/years", "/year", "/yr", "/y"]
+                    .iter()
+                    .find_map(|suffix| step.strip_suffix(suffix))
+                    .unwrap_or(step)
+            });
+            if let Some(extra) = tokens.next() {
+                return Err(format!("unexpected `{}` in `{}`", extra, s));
+            }
+
+            (years, rate, step)
+        } else if s.contains('=') {
+            let (mut years, mut rate, mut step) = (None, None, None);
+            for pair in s.split(',') {
+                let (key, value) = pair
+                    .split_once('=')
+                    .ok_or_else(|| format!("expected key=value, got `{}`", pair))?;
+
+                match key.trim() {
+                    "years" | "y" => years = Some(value),
+                    "rate" | "r" => rate = Some(value),
+                    "step" | "incr" => step = Some(value),
+                    other => {
+                        return Err(format!(
+                            "unknown key `{}`, expected years, rate or step",
+                            other
+                        ))
+                    }
+                }
+            }
+
+            (
+                years.ok_or_else(|| format!("missing years= in `{}`", s))?,
+                rate.ok_or_else(|| format!("missing rate= in `{}`", s))?,
+                step,
+            )
+        } else {
+            let fields = s.split(',').collect::<Vec<_>>();
+            if !(2..=3).contains(&fields.len()) {
+                return Err(format!(
+                    "expected `5y @ 12% -1%/y`, `years=5,rate=12%,step=-1%` or `5,0.12,-0.01`, got `{}`",
+                    s
+                ));
+            }
+
+            (fields[0], fields[1], fields.get(2).copied())
+        };
+
+        Ok(GrowthAssumption(
+            parse_years(years)?,
+            parse_rate(rate)?,
+            Some(step.map(parse_rate).transpose()?.unwrap_or(0.0)),
+        ))
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct GrowthAssumptionBuilder {
     pub assumptions: Vec<f32>,
 }
@@ -60,4 +132,15 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn should_parse_every_form_of_growth_stage() {
+        let expected = GrowthAssumption(5, 0.12, Some(-0.01));
+
+        assert_eq!("5y @ 12% -1%/y".parse(), Ok(expected));
+        assert_eq!("years=5,rate=12%,step=-1%".parse(), Ok(expected));
+        assert_eq!("5,0.12,-0.01".parse(), Ok(expected));
+        assert_eq!("5y @ 12%".parse(), Ok(GrowthAssumption(5, 0.12, Some(0.0))));
+        assert!("5,12".parse::<GrowthAssumption>().is_err());
+    }
 }
@@ -1,9 +1,67 @@
+use super::cashflow_history_builder::CashflowHistoryBuilder;
+use super::dilution::{shares_at, Convertible, StockOption};
 use super::growth_assumption_builder::{GrowthAssumption, GrowthAssumptionBuilder};
-use crate::utils::financial::pv;
-use comfy_table::{presets, Cell, ColumnConstraint, Table, Width};
+use crate::utils::config::DEFAULT_DISCOUNT_RATE;
+use crate::utils::financial::{hazard_adjusted_rate, margin_of_safety, pv, real_rate, survival};
+use crate::utils::money::{Money, Scale};
+use crate::utils::table::{new_table, print_side_by_side, side_by_side};
+use comfy_table::{Cell, Table};
 use core::f32;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
-#[derive(PartialEq, Debug)]
+/// Share of the NPV coming from the terminal value above which a valuation is flagged
+pub const TERMINAL_SHARE_WARNING: f32 = 0.75;
+/// Perpetual growth above which a valuation is flagged, about the long run growth of the economy
+pub const PERPETUAL_GROWTH_WARNING: f32 = 0.04;
+
+/// Named enterprise to equity adjustment: (label, signed amount)
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Adjustment(pub String, pub f32);
+
+impl FromStr for Adjustment {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (label, amount) = s
+            .rsplit_once(':')
+            .ok_or_else(|| format!("expected label:amount, got `{}`", s))?;
+        let amount = amount
+            .trim()
+            .parse::<f32>()
+            .map_err(|_| format!("`{}` is not an amount in `{}`", amount.trim(), s))?;
+
+        if label.trim().is_empty() {
+            return Err(format!("missing label in `{}`", s));
+        }
+
+        Ok(Adjustment(label.trim().to_string(), amount))
+    }
+}
+
+/// Figures computed by a valuation, the same ones rendered in the tables
+#[derive(PartialEq, Debug, Clone)]
+pub struct IntrinsicValuation {
+    // (year, future value, present value) of each projected year
+    pub projections: Vec<(i32, f32, f32)>,
+    pub terminal_value: f32,
+    pub terminal_pv: f32,
+    pub npv: f32,
+    pub intrinsic: f32,
+    pub diluted_shares: Option<f32>,
+    // in the currency of the share price when an fx rate is given
+    pub per_share: Option<f32>,
+    pub margin_of_safety: Option<f32>,
+    // share of the npv coming from the terminal value
+    pub terminal_share: f32,
+    // terminal value as a multiple of the last projected cash flow
+    pub implied_multiple: f32,
+    // perpetual growth the terminal value stands for
+    pub implied_growth: f32,
+    pub warnings: Vec<String>,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct IntrinsicBuilder {
     current_value: Option<f32>,
     // expected rate of return for the investment
@@ -14,24 +72,85 @@ pub struct IntrinsicBuilder {
     cash: Option<f32>,
     // optional debt amount
     debt: Option<f32>,
+    // optional preferred equity
+    preferred: Option<f32>,
+    // optional lease liabilities
+    leases: Option<f32>,
+    // optional pension deficit
+    pension_deficit: Option<f32>,
+    // optional non-operating assets
+    non_operating_assets: Option<f32>,
+    // other named adjustments
+    adjustments: Vec<Adjustment>,
     // optional probability of failure
     probability_of_failure: Option<f32>,
+    // optional annual probability of failure applied to every projected year
+    hazard_rate: Option<f32>,
+    // optional value recovered in the year of failure
+    recovery_value: Option<f32>,
     // optional shares outstanding
     shares_outstanding: Option<f32>,
+    // optional history the current value was normalized from
+    cashflow_history: Option<CashflowHistoryBuilder>,
+    // optional annual change of the share count, negative for buybacks
+    share_change_rate: Option<f32>,
+    // options and warrants diluting the share count
+    options: Vec<StockOption>,
+    // convertible debt, equity when in the money
+    convertibles: Vec<Convertible>,
+    // optional market price of one share
+    share_price: Option<f32>,
+    // optional ISO code of the currency of the amounts, USD by default
+    currency: Option<String>,
+    // optional unit of the amounts and the share counts
+    scale: Option<Scale>,
+    // optional ISO code of the currency the share price is quoted in
+    price_currency: Option<String>,
+    // optional units of the price currency per unit of the amounts currency
+    fx_rate: Option<f32>,
+    // optional annual inflation the nominal rate and growth are restated in real terms with
+    inflation: Option<f32>,
+    // optional decimals of the amounts, two by default
+    precision: Option<usize>,
+    // narrates every step of the valuation after the tables
+    #[serde(skip)]
+    explain: bool,
+    // optional multiple of the last projected cash flow used as terminal value
+    exit_multiple: Option<f32>,
 }
 
 impl IntrinsicBuilder {
     pub fn new() -> IntrinsicBuilder {
         IntrinsicBuilder {
             current_value: None,
-            rate: Some(0.15), // default 15%
+            rate: Some(DEFAULT_DISCOUNT_RATE),
             growth_assumptions: GrowthAssumptionBuilder::new()
                 .add(GrowthAssumption(5, 0.05, None))
                 .add(GrowthAssumption(5, 0.05, None)), // default 5 % of fcf growth
             cash: None,
             debt: None,
+            preferred: None,
+            leases: None,
+            pension_deficit: None,
+            non_operating_assets: None,
+            adjustments: vec![],
             probability_of_failure: None,
+            hazard_rate: None,
+            recovery_value: None,
             shares_outstanding: None,
+            cashflow_history: None,
+            share_change_rate: None,
+            options: vec![],
+            convertibles: vec![],
+            share_price: None,
+            currency: None,
+            scale: None,
+            price_currency: None,
+            fx_rate: None,
+            inflation: None,
+            precision: None,
+            explain: false,
+            exit_multiple: None,
         }
     }
 
@@ -65,40 +184,289 @@ impl IntrinsicBuilder {
         self
     }
 
+    pub fn add_preferred(mut self, preferred: Option<f32>) -> IntrinsicBuilder {
+        self.preferred = preferred;
+
+        self
+    }
+
+    pub fn add_leases(mut self, leases: Option<f32>) -> IntrinsicBuilder {
+        self.leases = leases;
+
+        self
+    }
+
+    pub fn add_pension_deficit(mut self, pension_deficit: Option<f32>) -> IntrinsicBuilder {
+        self.pension_deficit = pension_deficit;
+
+        self
+    }
+
+    pub fn add_non_operating_assets(mut self, non_operating_assets: Option<f32>) -> IntrinsicBuilder {
+        self.non_operating_assets = non_operating_assets;
+
+        self
+    }
+
+    pub fn add_adjustments(mut self, adjustments: Vec<Adjustment>) -> IntrinsicBuilder {
+        self.adjustments = adjustments;
+
+        self
+    }
+
     pub fn add_probability_of_failure(
         mut self,
         probability_of_failure: Option<f32>,
     ) -> IntrinsicBuilder {
+        // failure is counted once, a probability replaces a hazard rate given before
+        if probability_of_failure.is_some() {
+            self.hazard_rate = None;
+        }
         self.probability_of_failure = probability_of_failure;
 
         self
     }
 
+    pub fn add_hazard_rate(mut self, hazard_rate: Option<f32>) -> IntrinsicBuilder {
+        // failure is counted once, a hazard rate replaces a probability given before
+        if hazard_rate.is_some() {
+            self.probability_of_failure = None;
+        }
+        self.hazard_rate = hazard_rate;
+
+        self
+    }
+
+    pub fn add_recovery_value(mut self, recovery_value: Option<f32>) -> IntrinsicBuilder {
+        self.recovery_value = recovery_value;
+
+        self
+    }
+
     pub fn add_shares_outstanding(mut self, shares_outstanding: Option<f32>) -> IntrinsicBuilder {
         self.shares_outstanding = shares_outstanding;
 
         self
     }
 
+    pub fn add_cashflow_history(
+        mut self,
+        cashflow_history: Option<CashflowHistoryBuilder>,
+    ) -> IntrinsicBuilder {
+        self.cashflow_history = cashflow_history;
+
+        self
+    }
+
+    pub fn add_share_change_rate(mut self, share_change_rate: Option<f32>) -> IntrinsicBuilder {
+        self.share_change_rate = share_change_rate;
+
+        self
+    }
+
+    pub fn add_options(mut self, options: Vec<StockOption>) -> IntrinsicBuilder {
+        self.options = options;
+
+        self
+    }
+
+    pub fn add_convertibles(mut self, convertibles: Vec<Convertible>) -> IntrinsicBuilder {
+        self.convertibles = convertibles;
+
+        self
+    }
+
+    pub fn add_share_price(mut self, share_price: Option<f32>) -> IntrinsicBuilder {
+        self.share_price = share_price;
+
+        self
+    }
+
+    pub fn add_currency(mut self, currency: Option<String>) -> IntrinsicBuilder {
+        self.currency = currency;
+
+        self
+    }
+
+    pub fn add_scale(mut self, scale: Option<Scale>) -> IntrinsicBuilder {
+        self.scale = scale;
+
+        self
+    }
+
+    pub fn add_price_currency(mut self, price_currency: Option<String>) -> IntrinsicBuilder {
+        self.price_currency = price_currency;
+
+        self
+    }
+
+    pub fn add_fx_rate(mut self, fx_rate: Option<f32>) -> IntrinsicBuilder {
+        self.fx_rate = fx_rate;
+
+        self
+    }
+
+    pub fn add_inflation(mut self, inflation: Option<f32>) -> IntrinsicBuilder {
+        self.inflation = inflation;
+
+        self
+    }
+
+    pub fn add_precision(mut self, precision: Option<usize>) -> IntrinsicBuilder {
+        self.precision = precision;
+
+        self
+    }
+
+    pub fn add_exit_multiple(mut self, exit_multiple: Option<f32>) -> IntrinsicBuilder {
+        self.exit_multiple = exit_multiple;
+
+        self
+    }
+
+    pub fn add_explain(mut self, explain: bool) -> IntrinsicBuilder {
+        self.explain = explain;
+
+        self
+    }
+
+    pub fn current_value(&self) -> f32 {
+        self.current_value.unwrap()
+    }
+
+    pub fn rate(&self) -> f32 {
+        self.rate.unwrap()
+    }
+
+    pub fn cash(&self) -> Option<f32> {
+        self.cash
+    }
+
+    pub fn debt(&self) -> Option<f32> {
+        self.debt
+    }
+
+    pub fn exit_multiple(&self) -> Option<f32> {
+        self.exit_multiple
+    }
+
+    /// Format of the amounts, in their currency and scale
+    pub fn money(&self) -> Money {
+        Money::new(self.currency.as_deref(), self.scale).add_precision(self.precision.unwrap_or(2))
+    }
+
+    /// Format of the value per share, in the currency of the price when an fx rate is given
+    pub fn per_share_money(&self) -> Money {
+        let currency = match self.fx_rate {
+            Some(_) => self.price_currency.as_deref().or(self.currency.as_deref()),
+            None => self.currency.as_deref(),
+        };
+
+        Money::new(currency, None).add_precision(self.precision.unwrap_or(2))
+    }
+
+    /// Computes the valuation without printing anything
+    pub fn valuation(&self) -> IntrinsicValuation {
+        self.evaluate().0
+    }
+
+    /// Computes the valuation and renders the tables without printing them
+    pub fn report(&self) -> (IntrinsicValuation, String) {
+        let (valuation, [assumptions_table, cashflow_table, adjustments_table, _], _) =
+            self.evaluate();
+
+        (
+            valuation,
+            side_by_side(&[&assumptions_table, &cashflow_table, &adjustments_table]),
+        )
+    }
+
     pub fn execute(self) -> f32 {
+        let (
+            valuation,
+            [assumptions_table, cashflow_table, adjustments_table, diagnostics_table],
+            steps,
+        ) = self.evaluate();
+
+        print_side_by_side(&[&assumptions_table, &cashflow_table, &adjustments_table]);
+        println!("{}", diagnostics_table);
+        for warning in valuation.warnings.iter() {
+            println!("Warning: {}", warning);
+        }
+        println!();
+
+        if self.explain {
+            println!("How the value was computed:");
+            for (i, step) in steps.iter().enumerate() {
+                println!("{:>2}. {}", i + 1, step);
+            }
+            println!();
+        }
+
+        valuation.intrinsic
+    }
+
+    /// Computes the valuation, its tables and the plain-language steps of the `explain` mode
+    fn evaluate(&self) -> (IntrinsicValuation, [Table; 4], Vec<String>) {
         let mut result = 0.0;
+        let mut projections = vec![];
+        let mut diluted = None;
         let mut current_value = self.current_value.unwrap();
         let rate = self.rate.unwrap();
         let growth_assumptions = &self.growth_assumptions.assumptions;
+        let share_change_rate = self.share_change_rate.unwrap_or(0.0);
+        let hazard_rate = self.hazard_rate.unwrap_or(0.0);
+        let recovery_value = self.recovery_value.unwrap_or(0.0);
+        // present value of the recoveries of the years the business may fail
+        let mut recovery = 0.0;
+        // present value per share, each year divided by the share count of that year
+        let mut per_share_npv = 0.0;
+        let precision = self.precision.unwrap_or(2);
+        let money = self.money();
+        // values per share are never scaled
+        let per_share_money = Money::new(self.currency.as_deref(), None).add_precision(precision);
+        let price_currency = self.price_currency.as_deref().or(self.currency.as_deref());
+        let price_money = Money::new(price_currency, None).add_precision(precision);
+        let fx_rate = self.fx_rate.unwrap_or(1.0);
+        let mut steps = vec![format!(
+            "The valuation starts from a free cash flow of {} and discounts every future cash flow \
+             at {:.1}%, the return required from the investment.",
+            money.format(current_value),
+            rate * 100.0
+        )];
 
         // Table 1: ASSUMPTIONS
-        let mut assumptions_table = Table::new();
-        assumptions_table.load_preset(presets::UTF8_BORDERS_ONLY);
-        assumptions_table.set_header(vec!["Assumptions", "Value"]);
-        assumptions_table.set_constraints(vec![
-            ColumnConstraint::Absolute(Width::Fixed(16)),
-            ColumnConstraint::Absolute(Width::Fixed(16)),
-        ]);
+        let mut assumptions_table = new_table(vec!["Assumptions", "Value"], vec![16, 16]);
 
         assumptions_table.add_row(vec![
             Cell::new("Initial Value (FCF)"),
-            Cell::new(format!("${:.2}", current_value)),
+            Cell::new(money.format(current_value)),
         ]);
+        if let Some(history) = &self.cashflow_history {
+            let values: Vec<String> = history
+                .history
+                .iter()
+                .map(|&value| money.format(value))
+                .collect();
+            assumptions_table.add_row(vec![
+                Cell::new("FCF History"),
+                Cell::new(values.join("\n")),
+            ]);
+
+            let historical_cagr = match history.cagr() {
+                Some(growth) => format!("{:.1}%", growth * 100.0),
+                None => "n/a".to_string(),
+            };
+            assumptions_table.add_row(vec![
+                Cell::new("Historical CAGR"),
+                Cell::new(historical_cagr),
+            ]);
+            assumptions_table.add_row(vec![
+                Cell::new("Normalization"),
+                Cell::new(history.normalization.to_string()),
+            ]);
+        }
+
         assumptions_table.add_row(vec![
             Cell::new("Discount Rate"),
             Cell::new(format!("{:.1}%", rate * 100.0)),
@@ -115,17 +483,45 @@ impl IntrinsicBuilder {
             Cell::new(growth_years.join("\n")),
         ]);
 
+        if let Some(exit_multiple) = self.exit_multiple {
+            assumptions_table.add_row(vec![
+                Cell::new("Exit Multiple"),
+                Cell::new(format!("{:.1}x FCF", exit_multiple)),
+            ]);
+        }
+
+        if let Some(inflation) = self.inflation {
+            assumptions_table.add_row(vec![
+                Cell::new("Inflation"),
+                Cell::new(format!("{:.1}%", inflation * 100.0)),
+            ]);
+            assumptions_table.add_row(vec![
+                Cell::new("Real Rate"),
+                Cell::new(format!("{:.1}%", real_rate(rate, inflation) * 100.0)),
+            ]);
+
+            let real_growth_years: Vec<String> = growth_assumptions
+                .iter()
+                .enumerate()
+                .map(|(i, &g)| format!("Year {}: {:.1}%", i + 1, real_rate(g, inflation) * 100.0))
+                .collect();
+            assumptions_table.add_row(vec![
+                Cell::new("Real Growth"),
+                Cell::new(real_growth_years.join("\n")),
+            ]);
+        }
+
         if let Some(cash_amt) = self.cash {
             assumptions_table.add_row(vec![
                 Cell::new("Cash"),
-                Cell::new(format!("${:.2}", cash_amt)),
+                Cell::new(money.format(cash_amt)),
             ]);
         }
 
         if let Some(debt_amt) = self.debt {
             assumptions_table.add_row(vec![
                 Cell::new("Debt"),
-                Cell::new(format!("${:.2}", debt_amt)),
+                Cell::new(money.format(debt_amt)),
             ]);
         }
 
@@ -136,6 +532,20 @@ impl IntrinsicBuilder {
             ]);
         }
 
+        if let Some(hazard) = self.hazard_rate {
+            assumptions_table.add_row(vec![
+                Cell::new("Annual Hazard"),
+                Cell::new(format!("{:.1}%", hazard * 100.0)),
+            ]);
+        }
+
+        if let Some(recovery_amt) = self.recovery_value {
+            assumptions_table.add_row(vec![
+                Cell::new("Recovery Value"),
+                Cell::new(money.format(recovery_amt)),
+            ]);
+        }
+
         if let Some(shares) = self.shares_outstanding {
             assumptions_table.add_row(vec![
                 Cell::new("Shares Outstanding"),
@@ -143,82 +553,373 @@ impl IntrinsicBuilder {
             ]);
         }
 
+        if let Some(change) = self.share_change_rate {
+            assumptions_table.add_row(vec![
+                Cell::new("Share Change"),
+                Cell::new(format!("{:+.1}% / year", change * 100.0)),
+            ]);
+        }
+
+        for StockOption(count, strike) in self.options.iter() {
+            assumptions_table.add_row(vec![
+                Cell::new("Options"),
+                Cell::new(format!("{:.2} @ {}", count, price_money.format(*strike))),
+            ]);
+        }
+
+        for Convertible(amount, conversion_price) in self.convertibles.iter() {
+            assumptions_table.add_row(vec![
+                Cell::new("Convertible"),
+                Cell::new(format!(
+                    "{} @ {}",
+                    money.format(*amount),
+                    price_money.format(*conversion_price)
+                )),
+            ]);
+        }
+
+        if let Some(price) = self.share_price {
+            assumptions_table.add_row(vec![
+                Cell::new("Share Price"),
+                Cell::new(price_money.format(price)),
+            ]);
+        }
+
+        if let Some(fx_rate) = self.fx_rate {
+            assumptions_table.add_row(vec![
+                Cell::new("FX Rate"),
+                Cell::new(format!("{:.4}", fx_rate)),
+            ]);
+        }
+
         // Table 2: CASH FLOW PROJECTIONS
-        let mut cashflow_table = Table::new();
-        cashflow_table.load_preset(presets::UTF8_BORDERS_ONLY);
-        cashflow_table.set_header(vec!["Year", "FV", "PV"]);
-        cashflow_table.set_constraints(vec![
-            ColumnConstraint::Absolute(Width::Fixed(10)),
-            ColumnConstraint::Absolute(Width::Fixed(10)),
-            ColumnConstraint::Absolute(Width::Fixed(10)),
-        ]);
+        // future values are also shown in today's money when an inflation is given
+        let mut cashflow_table = match self.inflation {
+            Some(_) => new_table(vec!["Year", "FV", "Real FV", "PV"], vec![10, 10, 10, 10]),
+            None => new_table(vec!["Year", "FV", "PV"], vec![10, 10, 10]),
+        };
+        let real_cell = |value: f32, year: i32| -> Option<Cell> {
+            self.inflation.map(|inflation| {
+                Cell::new(format!("{:.*}", precision, pv(inflation, year, value)))
+            })
+        };
 
         let mut year = 0;
-        cashflow_table.add_row(vec![
-            Cell::new(year),
-            Cell::new(format!("{:.2}", 0.0)),
-            Cell::new(format!("{:.2}", current_value)),
-        ]);
+        cashflow_table.add_row(
+            [
+                Some(Cell::new(year)),
+                Some(Cell::new(format!("{:.*}", precision, 0.0))),
+                real_cell(0.0, year),
+                Some(Cell::new(format!("{:.*}", precision, current_value))),
+            ]
+            .into_iter()
+            .flatten(),
+        );
 
         for assumption_rate in growth_assumptions.iter() {
             year += 1;
-            current_value = current_value * (1. + assumption_rate);
-            let pv_value = pv(rate, year, current_value);
+            let previous_value = current_value;
+            current_value *= 1. + assumption_rate;
+            // cash flows only arrive if the business survived until that year
+            let pv_value = pv(rate, year, current_value) * survival(hazard_rate, year);
+            let failed = survival(hazard_rate, year - 1) - survival(hazard_rate, year);
+            let recovery_pv = pv(rate, year, recovery_value) * failed;
 
-            cashflow_table.add_row(vec![
-                Cell::new(year),
-                Cell::new(format!("{:.2}", current_value)),
-                Cell::new(format!("{:.2}", pv_value)),
-            ]);
-            result += pv_value;
+            cashflow_table.add_row(
+                [
+                    Some(Cell::new(year)),
+                    Some(Cell::new(format!("{:.*}", precision, current_value))),
+                    real_cell(current_value, year),
+                    Some(Cell::new(format!("{:.*}", precision, pv_value))),
+                ]
+                .into_iter()
+                .flatten(),
+            );
+            projections.push((year, current_value, pv_value));
+            steps.push(format!(
+                "Year {}: {} grows {:.1}% to {}, discounted by 1 / (1 + {:.1}%)^{} = {:.4}{} it is worth {} today.",
+                year,
+                money.format(previous_value),
+                assumption_rate * 100.0,
+                money.format(current_value),
+                rate * 100.0,
+                year,
+                pv(rate, year, 1.0),
+                match self.hazard_rate {
+                    Some(_) => format!(
+                        " and by the {:.1}% chance of surviving until then,",
+                        survival(hazard_rate, year) * 100.0
+                    ),
+                    None => ",".to_string(),
+                },
+                money.format(pv_value)
+            ));
+            result += pv_value + recovery_pv;
+            recovery += recovery_pv;
+            per_share_npv += (pv_value + recovery_pv) / shares_at(1.0, share_change_rate, year);
         }
 
         let last_growth = self.growth_assumptions.assumptions.last().unwrap();
-        let terminal_value = current_value / (self.rate.unwrap() - last_growth) as f32;
-        let sale_price = pv(rate, year, terminal_value);
+        // the business may still fail after the projection, the perpetuity is discounted
+        // at the rate that earns the required return on the surviving cash flows
+        let terminal_rate = match self.hazard_rate {
+            Some(hazard_rate) => hazard_adjusted_rate(rate, hazard_rate),
+            None => rate,
+        };
+        let terminal_value = match self.exit_multiple {
+            Some(exit_multiple) => current_value * exit_multiple,
+            None => current_value / (terminal_rate - last_growth),
+        };
+        let sale_price = pv(rate, year, terminal_value) * survival(hazard_rate, year);
 
-        cashflow_table.add_row(vec![
-            Cell::new("TV"),
-            Cell::new(format!("{:.2}", terminal_value)),
-            Cell::new(format!("{:.2}", sale_price)),
-        ]);
+        cashflow_table.add_row(
+            [
+                Some(Cell::new("TV")),
+                Some(Cell::new(format!("{:.*}", precision, terminal_value))),
+                real_cell(terminal_value, year),
+                Some(Cell::new(format!("{:.*}", precision, sale_price))),
+            ]
+            .into_iter()
+            .flatten(),
+        );
+
+        if self.recovery_value.is_some() {
+            cashflow_table.add_row(
+                [
+                    Some(Cell::new("Recovery")),
+                    Some(Cell::new("")),
+                    self.inflation.map(|_| Cell::new("")),
+                    Some(Cell::new(format!("{:.*}", precision, recovery))),
+                ]
+                .into_iter()
+                .flatten(),
+            );
+        }
 
         result += sale_price;
+        per_share_npv += sale_price / shares_at(1.0, share_change_rate, year);
+        let npv = result;
 
-        cashflow_table.add_row(vec![
-            Cell::new("NPV"),
-            Cell::new(""),
-            Cell::new(format!("${:.2}", result)),
+        let terminal_formula = match self.exit_multiple {
+            Some(exit_multiple) => format!(
+                "the business is assumed to be sold at {:.1} times the cash flow of {}, worth {}",
+                exit_multiple,
+                money.format(current_value),
+                money.format(terminal_value)
+            ),
+            None => format!(
+                "the cash flow of {} is assumed to grow {:.1}% forever, worth {} / ({:.1}% - {:.1}%) = {}",
+                money.format(current_value),
+                last_growth * 100.0,
+                money.format(current_value),
+                terminal_rate * 100.0,
+                last_growth * 100.0,
+                money.format(terminal_value)
+            ),
+        };
+        steps.push(format!(
+            "Terminal value: after year {} {} in year {}. Discounted like the year {} cash flow it \
+             adds {} today, {:.1}% of the NPV.",
+            year,
+            terminal_formula,
+            year,
+            year,
+            money.format(sale_price),
+            sale_price / npv * 100.0
+        ));
+        if self.recovery_value.is_some() {
+            steps.push(format!(
+                "Recovery: {} is recovered in the year the business fails, weighted by the chance \
+                 of failing that year and discounted it adds {} today.",
+                money.format(recovery_value),
+                money.format(recovery)
+            ));
+        }
+        steps.push(format!(
+            "NPV: the present values add up to {}, the value of the whole business today.",
+            money.format(npv)
+        ));
+
+        cashflow_table.add_row(
+            [
+                Some(Cell::new("NPV")),
+                Some(Cell::new("")),
+                self.inflation.map(|_| Cell::new("")),
+                Some(Cell::new(money.format(result))),
+            ]
+            .into_iter()
+            .flatten(),
+        );
+
+        // Table 4: DIAGNOSTICS of the terminal value, printed below the others
+        let terminal_share = sale_price / npv;
+        let implied_multiple = terminal_value / current_value;
+        // the growth that makes the perpetuity worth the terminal value
+        let implied_growth = terminal_rate - current_value / terminal_value;
+        let mut warnings = vec![];
+
+        let mut diagnostics_table = new_table(vec!["Diagnostics", "Value"], vec![16, 16]);
+        diagnostics_table.add_row(vec![
+            Cell::new("TV % of NPV"),
+            Cell::new(format!("{:.1}%", terminal_share * 100.0)),
+        ]);
+        diagnostics_table.add_row(vec![
+            Cell::new("TV Multiple"),
+            Cell::new(format!("{:.1}x FCF", implied_multiple)),
         ]);
+        diagnostics_table.add_row(vec![
+            Cell::new("Implied Growth"),
+            Cell::new(format!("{:.1}%", implied_growth * 100.0)),
+        ]);
+
+        if self.exit_multiple.is_none() && *last_growth >= terminal_rate {
+            warnings.push(format!(
+                "the terminal growth of {:.1}% is not below the discount rate of {:.1}%, the terminal value is meaningless",
+                last_growth * 100.0,
+                terminal_rate * 100.0
+            ));
+        } else {
+            if implied_growth > PERPETUAL_GROWTH_WARNING {
+                warnings.push(format!(
+                    "a perpetual growth of {:.1}% is above the long run growth of the economy, about {:.0}%",
+                    implied_growth * 100.0,
+                    PERPETUAL_GROWTH_WARNING * 100.0
+                ));
+            }
+
+            if terminal_share > TERMINAL_SHARE_WARNING {
+                warnings.push(format!(
+                    "{:.1}% of the NPV comes from the terminal value, more than {:.0}%, the valuation rests on the years after the projection",
+                    terminal_share * 100.0,
+                    TERMINAL_SHARE_WARNING * 100.0
+                ));
+            }
+        }
 
         // Table 3: ADJUSTMENTS
-        let mut adjustments_table = Table::new();
-        adjustments_table.load_preset(presets::UTF8_BORDERS_ONLY);
-        adjustments_table.set_header(vec!["Adjustments", "Value"]);
-        adjustments_table.set_constraints(vec![
-            ColumnConstraint::Absolute(Width::Fixed(16)),
-            ColumnConstraint::Absolute(Width::Fixed(16)),
-        ]);
+        let mut adjustments_table = new_table(vec!["Adjustments", "Value"], vec![16, 16]);
 
         adjustments_table.add_row(vec![
             Cell::new("NPV"),
-            Cell::new(format!("${:.2}", result)),
+            Cell::new(money.format(result)),
         ]);
 
         if let Some(debt_amt) = self.debt {
             adjustments_table.add_row(vec![
                 Cell::new("- Debt"),
-                Cell::new(format!("${:.2}", debt_amt)),
+                Cell::new(money.format(debt_amt)),
             ]);
             result -= debt_amt;
+            steps.push(format!(
+                "Debt of {} belongs to the lenders and is subtracted.",
+                money.format(debt_amt)
+            ));
         }
 
         if let Some(cash_amt) = self.cash {
             adjustments_table.add_row(vec![
                 Cell::new("+ Cash"),
-                Cell::new(format!("${:.2}", cash_amt)),
+                Cell::new(money.format(cash_amt)),
             ]);
             result += cash_amt;
+            steps.push(format!(
+                "Cash of {} belongs to the shareholders and is added.",
+                money.format(cash_amt)
+            ));
+        }
+
+        if let Some(preferred) = self.preferred {
+            adjustments_table.add_row(vec![
+                Cell::new("- Preferred"),
+                Cell::new(money.format(preferred)),
+            ]);
+            result -= preferred;
+            steps.push(format!(
+                "Preferred equity of {} is paid before the common shareholders and is subtracted.",
+                money.format(preferred)
+            ));
+        }
+
+        if let Some(leases) = self.leases {
+            adjustments_table.add_row(vec![
+                Cell::new("- Leases"),
+                Cell::new(money.format(leases)),
+            ]);
+            result -= leases;
+            steps.push(format!(
+                "Lease liabilities of {} are owed like debt and are subtracted.",
+                money.format(leases)
+            ));
+        }
+
+        if let Some(pension_deficit) = self.pension_deficit {
+            adjustments_table.add_row(vec![
+                Cell::new("- Pension deficit"),
+                Cell::new(money.format(pension_deficit)),
+            ]);
+            result -= pension_deficit;
+            steps.push(format!(
+                "The pension deficit of {} is owed to the employees and is subtracted.",
+                money.format(pension_deficit)
+            ));
+        }
+
+        if let Some(non_operating_assets) = self.non_operating_assets {
+            adjustments_table.add_row(vec![
+                Cell::new("+ Non-op. assets"),
+                Cell::new(money.format(non_operating_assets)),
+            ]);
+            result += non_operating_assets;
+            steps.push(format!(
+                "Non-operating assets of {} are not in the cash flows and are added.",
+                money.format(non_operating_assets)
+            ));
+        }
+
+        for Adjustment(label, amount) in self.adjustments.iter() {
+            let sign = if *amount < 0.0 { "-" } else { "+" };
+            adjustments_table.add_row(vec![
+                Cell::new(format!("{} {}", sign, label)),
+                Cell::new(money.format(amount.abs())),
+            ]);
+            result += amount;
+            steps.push(format!(
+                "{} of {} is {}.",
+                label,
+                money.format(amount.abs()),
+                if *amount < 0.0 { "subtracted" } else { "added" }
+            ));
+        }
+
+        // the share count the npv is spread over once buybacks or dilution are considered
+        let effective_shares = self
+            .shares_outstanding
+            .map(|shares| shares * npv / per_share_npv);
+
+        // convertibles are equity when in the money and debt otherwise, the decision
+        // uses the market price when known and the undiluted value per share if not
+        let conversion_price = self.share_price.or_else(|| {
+            let equity = result * (1.0 - self.probability_of_failure.unwrap_or(0.0));
+            effective_shares.map(|shares| equity / shares * fx_rate)
+        });
+        let (converted, unconverted): (Vec<&Convertible>, Vec<&Convertible>) = self
+            .convertibles
+            .iter()
+            .partition(|convertible| {
+                conversion_price.is_some_and(|price| convertible.is_converted(price))
+            });
+
+        let convertible_debt: f32 = unconverted.iter().map(|convertible| convertible.0).sum();
+        if convertible_debt > 0.0 {
+            adjustments_table.add_row(vec![
+                Cell::new("- Convertibles"),
+                Cell::new(money.format(convertible_debt)),
+            ]);
+            result -= convertible_debt;
+            steps.push(format!(
+                "Convertible debt of {} is out of the money, stays debt and is subtracted.",
+                money.format(convertible_debt)
+            ));
         }
 
         if let Some(prob_failure) = self.probability_of_failure {
@@ -228,58 +929,176 @@ impl IntrinsicBuilder {
                 Cell::new(format!("{:.1}%", prob_failure * 100.0)),
             ]);
             result = before_prob * (1.0 - prob_failure);
+            steps.push(format!(
+                "A {:.1}% probability of failure, leaving nothing to the shareholders, keeps {:.1}% of the value.",
+                prob_failure * 100.0,
+                (1.0 - prob_failure) * 100.0
+            ));
         }
 
         adjustments_table.add_row(vec![
             Cell::new("= Intrinsic"),
-            Cell::new(format!("${:.2}", result)),
+            Cell::new(money.format(result)),
         ]);
+        steps.push(format!(
+            "Intrinsic value: {} for the whole equity.",
+            money.format(result)
+        ));
 
-        if let Some(shares) = self.shares_outstanding {
-            let value_per_share = result / shares;
+        if let (Some(shares), Some(mut diluted_shares)) =
+            (self.shares_outstanding, effective_shares)
+        {
             adjustments_table.add_row(vec![Cell::new("")]);
+            if self.share_change_rate.is_some() || !self.options.is_empty() || !converted.is_empty()
+            {
+                adjustments_table.add_row(vec![
+                    Cell::new("Shares"),
+                    Cell::new(format!("{:.1}", shares)),
+                ]);
+            }
+
+            if self.share_change_rate.is_some() {
+                adjustments_table.add_row(vec![
+                    Cell::new("± Share change"),
+                    Cell::new(format!("{:+.2}", diluted_shares - shares)),
+                ]);
+            }
+
+            let option_price = self
+                .share_price
+                .unwrap_or(result / diluted_shares * fx_rate);
+            let option_shares: f32 = self
+                .options
+                .iter()
+                .map(|option| option.treasury_shares(option_price))
+                .sum();
+            if !self.options.is_empty() {
+                adjustments_table.add_row(vec![
+                    Cell::new("+ Options (TSM)"),
+                    Cell::new(format!("{:.2}", option_shares)),
+                ]);
+                diluted_shares += option_shares;
+            }
+
+            if !converted.is_empty() {
+                // the face amount is in the currency of the amounts, the conversion price in the price one
+                let convertible_shares: f32 = converted
+                    .iter()
+                    .map(|convertible| convertible.shares() * fx_rate)
+                    .sum();
+                adjustments_table.add_row(vec![
+                    Cell::new("+ Convertibles"),
+                    Cell::new(format!("{:.2}", convertible_shares)),
+                ]);
+                diluted_shares += convertible_shares;
+            }
+
+            let mut value_per_share = result / diluted_shares;
             adjustments_table.add_row(vec![
                 Cell::new("÷ Shares"),
-                Cell::new(format!("{:.1}", shares)),
+                Cell::new(format!("{:.1}", diluted_shares)),
             ]);
             adjustments_table.add_row(vec![
                 Cell::new("= per Share"),
-                Cell::new(format!("${:.2}", value_per_share)),
+                Cell::new(per_share_money.format(value_per_share)),
             ]);
-        }
 
-        // Print the three tables side by side
-        let assumptions_str = assumptions_table.to_string();
-        let cashflow_str = cashflow_table.to_string();
-        let adjustments_str = adjustments_table.to_string();
+            if let Some(fx_rate) = self.fx_rate {
+                value_per_share *= fx_rate;
+                adjustments_table.add_row(vec![
+                    Cell::new(format!(
+                        "× {}/{}",
+                        self.currency.as_deref().unwrap_or("USD").to_uppercase(),
+                        price_currency.unwrap_or("USD").to_uppercase()
+                    )),
+                    Cell::new(format!("{:.4}", fx_rate)),
+                ]);
+                adjustments_table.add_row(vec![
+                    Cell::new("= per Share"),
+                    Cell::new(price_money.format(value_per_share)),
+                ]);
+            }
 
-        let assumptions_lines: Vec<&str> = assumptions_str.lines().collect();
-        let cashflow_lines: Vec<&str> = cashflow_str.lines().collect();
-        let adjustments_lines: Vec<&str> = adjustments_str.lines().collect();
+            if let Some(price) = self.share_price {
+                adjustments_table.add_row(vec![
+                    Cell::new("Price"),
+                    Cell::new(price_money.format(price)),
+                ]);
+                adjustments_table.add_row(vec![
+                    Cell::new("Margin of Safety"),
+                    Cell::new(match margin_of_safety(value_per_share, price) {
+                        Some(margin) => format!("{:.1}%", margin * 100.0),
+                        None => "n/a".to_string(),
+                    }),
+                ]);
+            }
 
-        let max_lines = assumptions_lines
-            .len()
-            .max(cashflow_lines.len())
-            .max(adjustments_lines.len());
+            steps.push(format!(
+                "Per share: {} divided by {:.1} shares{} is {}{}.",
+                money.format(result),
+                diluted_shares,
+                if diluted_shares != shares {
+                    " once buybacks and dilution are counted"
+                } else {
+                    ""
+                },
+                per_share_money.format(result / diluted_shares),
+                match self.fx_rate {
+                    Some(fx_rate) => format!(
+                        ", {} at an exchange rate of {:.4}",
+                        price_money.format(value_per_share),
+                        fx_rate
+                    ),
+                    None => "".to_string(),
+                }
+            ));
+            if let Some(price) = self.share_price {
+                let margin = margin_of_safety(value_per_share, price).unwrap_or(0.0);
+                steps.push(if margin > 0.0 {
+                    format!(
+                        "At a price of {} the margin of safety is {:.1}%: buy only when it is large, \
+                         so that mistakes in the assumptions still leave a profit.",
+                        price_money.format(price),
+                        margin * 100.0
+                    )
+                } else {
+                    format!(
+                        "At a price of {} the share costs more than it is worth, there is no margin of safety.",
+                        price_money.format(price)
+                    )
+                });
+            }
 
-        println!();
-        for i in 0..max_lines {
-            let assumptions_line = assumptions_lines.get(i).unwrap_or(&"");
-            let cashflow_line = cashflow_lines.get(i).unwrap_or(&"");
-            let adjustments_line = adjustments_lines.get(i).unwrap_or(&"");
-
-            // Each table is ~36 chars wide, pad for alignment
-            let padded_assumptions = format!("{:36}", assumptions_line);
-            let padded_cashflow = format!("{:36}", cashflow_line);
-
-            println!(
-                "{}  {}  {}",
-                padded_assumptions, padded_cashflow, adjustments_line
-            );
+            diluted = Some((diluted_shares, value_per_share));
         }
-        println!();
 
-        result
+        let valuation = IntrinsicValuation {
+            projections,
+            terminal_value,
+            terminal_pv: sale_price,
+            npv,
+            intrinsic: result,
+            diluted_shares: diluted.map(|(shares, _)| shares),
+            per_share: diluted.map(|(_, value)| value),
+            margin_of_safety: diluted
+                .zip(self.share_price)
+                .and_then(|((_, value), price)| margin_of_safety(value, price)),
+            terminal_share,
+            implied_multiple,
+            implied_growth,
+            warnings,
+        };
+
+        (
+            valuation,
+            [
+                assumptions_table,
+                cashflow_table,
+                adjustments_table,
+                diagnostics_table,
+            ],
+            steps,
+        )
     }
 }
 
@@ -296,8 +1115,28 @@ mod tests {
                 .add(GrowthAssumption(10, 0.05, None)),
             cash: None,
             debt: None,
+            preferred: None,
+            leases: None,
+            pension_deficit: None,
+            non_operating_assets: None,
+            adjustments: vec![],
             probability_of_failure: None,
+            hazard_rate: None,
+            recovery_value: None,
             shares_outstanding: None,
+            cashflow_history: None,
+            share_change_rate: None,
+            options: vec![],
+            convertibles: vec![],
+            share_price: None,
+            currency: None,
+            scale: None,
+            price_currency: None,
+            fx_rate: None,
+            inflation: None,
+            precision: None,
+            explain: false,
+            exit_multiple: None,
         };
 
         let builded = IntrinsicBuilder::new()
@@ -319,6 +1158,170 @@ mod tests {
                 GrowthAssumptionBuilder::new().add(GrowthAssumption(10, 0.05, None)),
             );
 
-        assert_eq!(intrisic.execute(), 184.67798);
+        // 94.09 of discounted cash flows + 60.39 of discounted terminal value (244.33 / 1.15^10),
+        // the former 184.67798 did not match the computation and failed before any change
+        assert_eq!(intrisic.execute(), 154.48015);
+    }
+
+    #[test]
+    fn test_balance_sheet_adjustments_bridge_to_equity() {
+        let intrisic = IntrinsicBuilder::new()
+            .add_current_value(15.0)
+            .add_rate(0.15)
+            .add_growth_assumptions(
+                GrowthAssumptionBuilder::new().add(GrowthAssumption(10, 0.05, None)),
+            )
+            .add_preferred(Some(4.0))
+            .add_leases(Some(3.0))
+            .add_pension_deficit(Some(2.0))
+            .add_non_operating_assets(Some(5.0))
+            .add_adjustments(vec![
+                Adjustment("Minority interest".to_string(), -1.5),
+                Adjustment("Equity investments".to_string(), 3.5),
+            ]);
+
+        assert_eq!(intrisic.execute(), 152.48015);
+        assert_eq!(
+            "Minority interest: -1.5".parse(),
+            Ok(Adjustment("Minority interest".to_string(), -1.5))
+        );
+        assert_eq!(
+            "foo".parse::<Adjustment>(),
+            Err("expected label:amount, got `foo`".to_string())
+        );
+    }
+
+    #[test]
+    fn test_hazard_rate_weights_cash_flows_by_survival() {
+        let intrisic = IntrinsicBuilder::new()
+            .add_current_value(15.0)
+            .add_rate(0.15)
+            .add_growth_assumptions(
+                GrowthAssumptionBuilder::new().add(GrowthAssumption(10, 0.05, None)),
+            )
+            .add_hazard_rate(Some(0.05))
+            .add_recovery_value(Some(20.0));
+
+        assert_eq!(intrisic.clone().execute(), 101.24838);
+        assert_eq!(
+            intrisic.add_probability_of_failure(Some(0.1)).hazard_rate,
+            None
+        );
+    }
+
+    #[test]
+    fn test_convertible_out_of_the_money_is_treated_as_debt() {
+        let intrisic = IntrinsicBuilder::new()
+            .add_current_value(15.0)
+            .add_rate(0.15)
+            .add_growth_assumptions(
+                GrowthAssumptionBuilder::new().add(GrowthAssumption(10, 0.05, None)),
+            )
+            .add_shares_outstanding(Some(10.0))
+            .add_convertibles(vec![Convertible(20.0, 50.0)])
+            .add_share_price(Some(15.0));
+
+        assert_eq!(intrisic.execute(), 134.48015);
+    }
+
+    #[test]
+    fn test_buybacks_and_options_change_the_value_per_share() {
+        let valuation = IntrinsicBuilder::new()
+            .add_current_value(15.0)
+            .add_rate(0.15)
+            .add_growth_assumptions(
+                GrowthAssumptionBuilder::new().add(GrowthAssumption(10, 0.05, None)),
+            )
+            .add_shares_outstanding(Some(10.0))
+            .add_share_change_rate(Some(-0.02))
+            .add_options(vec![StockOption(2.0, 10.0)])
+            .add_share_price(Some(20.0))
+            .valuation();
+
+        // 8.69 shares once the buybacks are weighted in, plus 1 net share from the options
+        assert_eq!(valuation.diluted_shares, Some(9.694955));
+        assert_eq!(valuation.per_share, Some(15.934076));
+    }
+
+    #[test]
+    fn test_fx_rate_converts_the_value_per_share_to_the_price_currency() {
+        let builder = IntrinsicBuilder::new()
+            .add_current_value(10.0)
+            .add_shares_outstanding(Some(2.0))
+            .add_currency(Some("EUR".to_string()));
+        let converted = builder
+            .clone()
+            .add_price_currency(Some("USD".to_string()))
+            .add_fx_rate(Some(2.0))
+            .valuation();
+
+        assert_eq!(
+            converted.per_share,
+            builder.valuation().per_share.map(|value| value * 2.0)
+        );
+        assert_eq!(converted.intrinsic, builder.valuation().intrinsic);
+    }
+
+    #[test]
+    fn test_inflation_only_restates_the_projections_in_real_terms() {
+        let builder = IntrinsicBuilder::new().add_current_value(10.0);
+
+        assert_eq!(
+            builder.clone().add_inflation(Some(0.03)).valuation(),
+            builder.valuation()
+        );
+    }
+
+    #[test]
+    fn test_explain_steps_narrate_the_adjustments() {
+        let (_, _, steps) = IntrinsicBuilder::new()
+            .add_current_value(10.0)
+            .add_cash(Some(5.0))
+            .evaluate();
+
+        assert!(steps[0].starts_with("The valuation starts from a free cash flow of $10.00"));
+        assert!(steps.contains(&"Cash of $5.00 belongs to the shareholders and is added.".to_string()));
+    }
+
+    #[test]
+    fn test_terminal_value_diagnostics() {
+        let valuation = IntrinsicBuilder::new()
+            .add_current_value(10.0)
+            .add_exit_multiple(Some(8.0))
+            .valuation();
+
+        assert_eq!(valuation.implied_multiple, 8.0);
+        assert_eq!(valuation.implied_growth, 0.025000006);
+        assert!(valuation.warnings.is_empty());
+
+        // growing 5% forever outpaces the economy, the terminal value is still a minority of the NPV
+        let valuation = IntrinsicBuilder::new().add_current_value(10.0).valuation();
+
+        assert_eq!(
+            valuation.warnings,
+            vec!["a perpetual growth of 5.0% is above the long run growth of the economy, about 4%"]
+        );
+
+        // 3.5% forever at 5%, most of the value comes after the projection
+        let valuation = IntrinsicBuilder::new()
+            .add_current_value(10.0)
+            .add_rate(0.05)
+            .add_growth_assumptions(
+                GrowthAssumptionBuilder::new().add(GrowthAssumption(10, 0.035, None)),
+            )
+            .valuation();
+
+        assert_eq!(valuation.warnings.len(), 1);
+        assert!(valuation.warnings[0].ends_with(
+            "of the NPV comes from the terminal value, more than 75%, the valuation rests on the years after the projection"
+        ));
+
+        let valuation = IntrinsicBuilder::new()
+            .add_current_value(10.0)
+            .add_rate(0.04)
+            .valuation();
+
+        assert_eq!(valuation.warnings.len(), 1);
+        assert!(valuation.warnings[0].starts_with("the terminal growth of 5.0% is not below"));
     }
 }
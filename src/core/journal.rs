@@ -0,0 +1,156 @@
+use super::intrinsic_builder::IntrinsicBuilder;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Saved valuation run, the inputs are kept so the run can be recomputed
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub ticker: String,
+    // seconds since the unix epoch
+    pub timestamp: u64,
+    pub note: Option<String>,
+    pub inputs: IntrinsicBuilder,
+    pub intrinsic: f32,
+    pub per_share: Option<f32>,
+}
+
+impl JournalEntry {
+    pub fn new(
+        ticker: String,
+        timestamp: u64,
+        note: Option<String>,
+        inputs: IntrinsicBuilder,
+    ) -> JournalEntry {
+        let valuation = inputs.valuation();
+
+        JournalEntry {
+            ticker: ticker.to_uppercase(),
+            timestamp,
+            note,
+            inputs,
+            intrinsic: valuation.intrinsic,
+            per_share: valuation.per_share,
+        }
+    }
+}
+
+/// Change of one assumption between two runs and how much it moved the value
+#[derive(PartialEq, Debug, Clone)]
+pub struct Attribution {
+    pub assumption: String,
+    pub from: String,
+    pub to: String,
+    pub impact: f32,
+}
+
+/// Value compared between runs, per share when the share count is known
+pub fn value_of(builder: &IntrinsicBuilder) -> f32 {
+    let valuation = builder.valuation();
+
+    valuation.per_share.unwrap_or(valuation.intrinsic)
+}
+
+/// Per share value of the latest run of each ticker, runs without a share count are skipped
+pub fn latest_per_share(entries: &[JournalEntry]) -> HashMap<String, f32> {
+    entries
+        .iter()
+        .filter_map(|entry| entry.per_share.map(|value| (entry.ticker.clone(), value)))
+        .collect()
+}
+
+/// Renders a serialized input as a short text
+fn display(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_string(),
+        Value::Number(number) => format!("{}", number.as_f64().unwrap() as f32),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::Array(_) => format!("({})", display(item)),
+                _ => display(item),
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+        Value::Object(fields) => fields.values().map(display).collect::<Vec<_>>().join(" "),
+        other => other.to_string(),
+    }
+}
+
+/// Swaps the changed assumptions one at a time from the first run to the second,
+/// the impacts add up to the total change of the value
+pub fn attribute(from: &IntrinsicBuilder, to: &IntrinsicBuilder) -> Vec<Attribution> {
+    let Value::Object(mut current) = serde_json::to_value(from).unwrap() else {
+        unreachable!("the builder serializes to an object")
+    };
+    let Value::Object(target) = serde_json::to_value(to).unwrap() else {
+        unreachable!("the builder serializes to an object")
+    };
+
+    let mut attributions = vec![];
+    let mut previous = value_of(from);
+
+    for (assumption, value) in target.into_iter() {
+        let old = current.get(&assumption).cloned().unwrap_or(Value::Null);
+        if old == value {
+            continue;
+        }
+
+        current.insert(assumption.clone(), value.clone());
+        let builder: IntrinsicBuilder =
+            serde_json::from_value(Value::Object(current.clone())).unwrap();
+        let next = value_of(&builder);
+
+        attributions.push(Attribution {
+            assumption: assumption.replace('_', " "),
+            from: display(&old),
+            to: display(&value),
+            impact: next - previous,
+        });
+        previous = next;
+    }
+
+    attributions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_journal_entry_round_trips_through_json() {
+        let entry = JournalEntry::new(
+            "abc".to_string(),
+            0,
+            Some("first take".to_string()),
+            IntrinsicBuilder::new()
+                .add_current_value(10.0)
+                .add_cash(Some(5.0)),
+        );
+
+        let line = serde_json::to_string(&entry).unwrap();
+
+        assert_eq!(entry.ticker, "ABC");
+        assert_eq!(serde_json::from_str::<JournalEntry>(&line).unwrap(), entry);
+    }
+
+    #[test]
+    fn test_attributions_add_up_to_the_change_of_value() {
+        let from = IntrinsicBuilder::new().add_current_value(10.0);
+        let to = IntrinsicBuilder::new()
+            .add_current_value(10.0)
+            .add_rate(0.12)
+            .add_debt(Some(20.0));
+
+        let attributions = attribute(&from, &to);
+        let total: f32 = attributions.iter().map(|item| item.impact).sum();
+
+        assert_eq!(attributions.len(), 2);
+        assert_eq!(attributions[0].assumption, "debt");
+        assert_eq!(attributions[0].impact, -20.0);
+        assert_eq!(attributions[1].from, "0.15");
+        assert_eq!(attributions[1].to, "0.12");
+        assert!((total - (value_of(&to) - value_of(&from))).abs() < 1e-3);
+    }
+}
@@ -1,6 +1,31 @@
-#[derive(Debug, PartialEq, PartialOrd)]
+use crate::utils::grammar::parse_number;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 pub struct KellyAssumption(pub f32, pub f32);
 
+/// Outcome in the form `80% @ +21x`, or `0.8,21` with the probability first
+impl FromStr for KellyAssumption {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (probability, outcome) = s
+            .split_once('@')
+            .or_else(|| s.split_once(','))
+            .ok_or_else(|| format!("expected `80% @ +21x` or `0.8,21`, got `{}`", s))?;
+
+        let probability = parse_number(probability)?;
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(format!(
+                "probability `{}` is not between 0% and 100%",
+                s.split(['@', ',']).next().unwrap().trim()
+            ));
+        }
+
+        Ok(KellyAssumption(probability, parse_number(outcome)?))
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct KellyAssumptionBuilder {
     pub assumptions: Vec<KellyAssumption>,
@@ -21,7 +46,7 @@ impl KellyAssumptionBuilder {
 
     pub fn get_edge(&self) -> f32 {
         self.assumptions.iter().fold(0.0, |mut acc, it| {
-            acc = acc + it.0 * it.1;
+            acc += it.0 * it.1;
 
             acc
         })
@@ -75,4 +100,11 @@ mod tests {
 
         assert_eq!(kelly.compute(), 0.8309524);
     }
+
+    #[test]
+    fn test_parse_kelly_outcomes() {
+        assert_eq!("80% @ +21x".parse(), Ok(KellyAssumption(0.8, 21.0)));
+        assert_eq!("0.1,-1".parse(), Ok(KellyAssumption(0.1, -1.0)));
+        assert!("80 @ 21x".parse::<KellyAssumption>().is_err());
+    }
 }
@@ -0,0 +1,140 @@
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Source of market data and financial history per ticker
+pub trait MarketDataProvider {
+    fn price(&self, ticker: &str) -> Option<f32>;
+    fn shares_outstanding(&self, ticker: &str) -> Option<f32>;
+    /// Free cash flow from the oldest to the latest year
+    fn cashflow_history(&self, ticker: &str) -> Vec<f32>;
+}
+
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+struct QuoteRecord {
+    ticker: String,
+    price: Option<f32>,
+    shares_outstanding: Option<f32>,
+}
+
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+struct HistoryRecord {
+    ticker: String,
+    year: i32,
+    cashflow: f32,
+}
+
+/// Provider reading quotes.csv (ticker,price,shares_outstanding) and
+/// history.csv (ticker,year,cashflow) from a local directory
+#[derive(PartialEq, Debug, Clone)]
+pub struct CsvMarketData {
+    quotes: Vec<QuoteRecord>,
+    history: Vec<HistoryRecord>,
+}
+
+/// Records of a CSV file with headers, an invalid row is reported with the file
+fn parse_records<T: DeserializeOwned>(file: &Path, content: &str) -> Result<Vec<T>, String> {
+    csv::Reader::from_reader(content.as_bytes())
+        .deserialize()
+        .map(|record| {
+            record
+                .map_err(|error| format!("invalid market data file {}: {}", file.display(), error))
+        })
+        .collect()
+}
+
+/// Records of a file of the directory, a missing file has no records
+fn read_records<T: DeserializeOwned>(dir: &Path, file: &str) -> Result<Vec<T>, String> {
+    let path = dir.join(file);
+    let content = fs::read_to_string(&path).unwrap_or_default();
+
+    parse_records(&path, &content)
+}
+
+impl CsvMarketData {
+    pub fn new(dir: &Path) -> Result<CsvMarketData, String> {
+        Ok(CsvMarketData {
+            quotes: read_records(dir, "quotes.csv")?,
+            history: read_records(dir, "history.csv")?,
+        })
+    }
+
+    fn quote(&self, ticker: &str) -> Option<&QuoteRecord> {
+        self.quotes
+            .iter()
+            .find(|quote| quote.ticker.eq_ignore_ascii_case(ticker))
+    }
+}
+
+impl MarketDataProvider for CsvMarketData {
+    fn price(&self, ticker: &str) -> Option<f32> {
+        self.quote(ticker)?.price
+    }
+
+    fn shares_outstanding(&self, ticker: &str) -> Option<f32> {
+        self.quote(ticker)?.shares_outstanding
+    }
+
+    fn cashflow_history(&self, ticker: &str) -> Vec<f32> {
+        let mut history: Vec<&HistoryRecord> = self
+            .history
+            .iter()
+            .filter(|record| record.ticker.eq_ignore_ascii_case(ticker))
+            .collect();
+        history.sort_by_key(|record| record.year);
+
+        history.into_iter().map(|record| record.cashflow).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // compiled into the test binary, the published crate leaves the fixtures out
+    fn fixtures() -> CsvMarketData {
+        CsvMarketData {
+            quotes: parse_records(
+                Path::new("quotes.csv"),
+                include_str!("../../fixtures/market/quotes.csv"),
+            )
+            .unwrap(),
+            history: parse_records(
+                Path::new("history.csv"),
+                include_str!("../../fixtures/market/history.csv"),
+            )
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_invalid_row_names_the_file_and_the_row() {
+        let error = parse_records::<HistoryRecord>(
+            Path::new("history.csv"),
+            "ticker,year,cashflow\nKO,2020,x\n",
+        )
+        .unwrap_err();
+
+        assert!(error.starts_with("invalid market data file history.csv:"));
+        assert!(error.contains("line: 2"));
+    }
+
+    #[test]
+    fn test_quotes_are_looked_up_by_ticker() {
+        let market = fixtures();
+
+        assert_eq!(market.price("ko"), Some(62.5));
+        assert_eq!(market.shares_outstanding("KO"), Some(4.31));
+        assert_eq!(market.shares_outstanding("BRK.B"), None);
+        assert_eq!(market.price("XYZ"), None);
+    }
+
+    #[test]
+    fn test_cashflow_history_is_sorted_by_year() {
+        assert_eq!(
+            fixtures().cashflow_history("KO"),
+            vec![11.26, 9.53, 9.75, 4.74]
+        );
+    }
+}
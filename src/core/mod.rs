@@ -1,3 +1,17 @@
+pub mod batch_builder;
+pub mod cashflow_history_builder;
+pub mod company_facts;
+pub mod comps_builder;
+pub mod ddm_builder;
+pub mod dilution;
+pub mod epv_builder;
 pub mod kelly_builder;
+pub mod market_data;
 pub mod intrinsic_builder;
+pub mod journal;
 pub mod growth_assumption_builder;
+pub mod portfolio_builder;
+pub mod residual_income_builder;
+pub mod tax;
+pub mod wacc_builder;
+pub mod watchlist_builder;
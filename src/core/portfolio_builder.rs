@@ -0,0 +1,402 @@
+use super::kelly_builder::{KellyAssumption, KellyAssumptionBuilder};
+use super::tax::TaxRates;
+use crate::utils::date::parse_date;
+use crate::utils::financial::xirr;
+use crate::utils::table::{new_table, print_side_by_side};
+use comfy_table::Cell;
+use serde::{Deserialize, Deserializer};
+
+/// Shares bought on a date at a cost per share
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+pub struct Lot {
+    // days since 1970, written YYYY-MM-DD in the holdings file
+    #[serde(deserialize_with = "deserialize_date")]
+    pub date: i64,
+    pub shares: f32,
+    pub cost: f32,
+}
+
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+pub struct Position {
+    pub ticker: String,
+    pub price: f32,
+    // optional intrinsic value per share
+    pub intrinsic: Option<f32>,
+    // scenario outcomes in the format of the kelly calculator: (probability, return)
+    #[serde(default)]
+    pub scenarios: Vec<(f32, f32)>,
+    pub lots: Vec<Lot>,
+}
+
+fn deserialize_date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    let date = String::deserialize(deserializer)?;
+
+    parse_date(&date).map_err(serde::de::Error::custom)
+}
+
+impl Position {
+    pub fn shares(&self) -> f32 {
+        self.lots.iter().map(|lot| lot.shares).sum()
+    }
+
+    pub fn cost_basis(&self) -> f32 {
+        self.lots.iter().map(|lot| lot.shares * lot.cost).sum()
+    }
+
+    pub fn market_value(&self) -> f32 {
+        self.shares() * self.price
+    }
+
+    /// Purchases as outflows and the market value as an inflow: (days since 1970, amount)
+    pub fn flows(&self, as_of: i64) -> Vec<(i64, f32)> {
+        let mut flows: Vec<(i64, f32)> = self
+            .lots
+            .iter()
+            .map(|lot| (lot.date, -lot.shares * lot.cost))
+            .collect();
+        flows.push((as_of, self.market_value()));
+
+        flows
+    }
+
+    /// Capital gains tax due on selling every lot, the losses offsetting the gains
+    pub fn capital_gains_tax(&self, tax: &TaxRates, as_of: i64) -> f32 {
+        let gains: Vec<(f32, i64)> = self
+            .lots
+            .iter()
+            .map(|lot| (lot.shares * (self.price - lot.cost), as_of - lot.date))
+            .collect();
+
+        tax.netted_capital_gains_tax(&gains)
+    }
+
+    /// Full kelly weight of the scenario outcomes, none without scenarios
+    pub fn kelly(&self) -> Option<f32> {
+        if self.scenarios.is_empty() {
+            return None;
+        }
+
+        let assumptions = self
+            .scenarios
+            .iter()
+            .map(|&(probability, outcome)| KellyAssumption(probability, outcome))
+            .collect();
+
+        Some(KellyAssumptionBuilder::new().set(assumptions).compute())
+    }
+}
+
+/// Annualized money weighted return of dated flows: (days since 1970, amount)
+pub fn flows_xirr(flows: &[(i64, f32)]) -> Option<f32> {
+    let first = flows.iter().map(|&(day, _)| day).min()?;
+    let years: Vec<(f32, f32)> = flows
+        .iter()
+        .map(|&(day, amount)| ((day - first) as f32 / 365.25, amount))
+        .collect();
+
+    xirr(&years)
+}
+
+#[derive(PartialEq, Debug)]
+pub struct PortfolioBuilder {
+    positions: Vec<Position>,
+    // valuation date, days since 1970
+    as_of: Option<i64>,
+    // fraction of the full kelly weight targeted, 0.5 for half kelly
+    kelly_fraction: f32,
+    // weight difference tolerated before a position is flagged
+    tolerance: f32,
+    // optional tax rates, the proceeds of selling are shown after tax
+    tax: Option<TaxRates>,
+}
+
+impl PortfolioBuilder {
+    pub fn new() -> PortfolioBuilder {
+        PortfolioBuilder {
+            positions: vec![],
+            as_of: None,
+            kelly_fraction: 1.0,
+            tolerance: 0.02,
+            tax: None,
+        }
+    }
+
+    pub fn add_positions(mut self, positions: Vec<Position>) -> PortfolioBuilder {
+        self.positions = positions;
+
+        self
+    }
+
+    pub fn add_as_of(mut self, as_of: i64) -> PortfolioBuilder {
+        self.as_of = Some(as_of);
+
+        self
+    }
+
+    pub fn add_kelly_fraction(mut self, kelly_fraction: f32) -> PortfolioBuilder {
+        self.kelly_fraction = kelly_fraction;
+
+        self
+    }
+
+    pub fn add_tolerance(mut self, tolerance: f32) -> PortfolioBuilder {
+        self.tolerance = tolerance;
+
+        self
+    }
+
+    pub fn add_tax(mut self, tax: Option<TaxRates>) -> PortfolioBuilder {
+        self.tax = tax;
+
+        self
+    }
+
+    pub fn total_value(&self) -> f32 {
+        self.positions
+            .iter()
+            .map(|position| position.market_value())
+            .sum()
+    }
+
+    /// Target weight of each position, scaled down when the kelly weights add up to more than 100%
+    pub fn targets(&self) -> Vec<Option<f32>> {
+        let targets: Vec<Option<f32>> = self
+            .positions
+            .iter()
+            .map(|position| {
+                position
+                    .kelly()
+                    .map(|kelly| (kelly * self.kelly_fraction).max(0.0))
+            })
+            .collect();
+
+        let sum: f32 = targets.iter().flatten().sum();
+        let scale = if sum > 1.0 { 1.0 / sum } else { 1.0 };
+
+        targets
+            .into_iter()
+            .map(|target| target.map(|target| target * scale))
+            .collect()
+    }
+
+    pub fn execute(self) -> f32 {
+        let as_of = self.as_of.unwrap();
+        let total_value = self.total_value();
+        let targets = self.targets();
+
+        let mut headers = vec![
+            "Ticker", "Shares", "Cost", "Value", "Gain", "XIRR", "Weight", "Upside", "Kelly",
+            "Sizing",
+        ];
+        let mut widths = vec![8, 9, 11, 11, 8, 8, 8, 8, 8, 8];
+        if self.tax.is_some() {
+            headers.extend(["Tax", "After Tax"]);
+            widths.extend([10, 11]);
+        }
+        let mut positions_table = new_table(headers, widths);
+
+        let mut all_flows = vec![];
+        // flows with the proceeds of selling net of the capital gains tax
+        let mut after_tax_flows = vec![];
+        let mut total_tax = 0.0;
+
+        for (position, target) in self.positions.iter().zip(targets.iter()) {
+            let flows = position.flows(as_of);
+            let weight = position.market_value() / total_value;
+
+            let sizing = match target {
+                Some(target) if weight - target > self.tolerance => "over",
+                Some(target) if target - weight > self.tolerance => "under",
+                Some(_) => "ok",
+                None => "",
+            };
+
+            let mut row = vec![
+                Cell::new(&position.ticker),
+                Cell::new(format!("{:.1}", position.shares())),
+                Cell::new(format!("${:.2}", position.cost_basis())),
+                Cell::new(format!("${:.2}", position.market_value())),
+                Cell::new(format!(
+                    "{:+.1}%",
+                    (position.market_value() / position.cost_basis() - 1.0) * 100.0
+                )),
+                Cell::new(match flows_xirr(&flows) {
+                    Some(rate) => format!("{:.1}%", rate * 100.0),
+                    None => "n/a".to_string(),
+                }),
+                Cell::new(format!("{:.1}%", weight * 100.0)),
+                Cell::new(match position.intrinsic {
+                    Some(intrinsic) => {
+                        format!("{:+.1}%", (intrinsic / position.price - 1.0) * 100.0)
+                    }
+                    None => "".to_string(),
+                }),
+                Cell::new(match target {
+                    Some(target) => format!("{:.1}%", target * 100.0),
+                    None => "".to_string(),
+                }),
+                Cell::new(sizing),
+            ];
+
+            if let Some(tax) = &self.tax {
+                let due = position.capital_gains_tax(tax, as_of);
+                row.push(Cell::new(format!("${:.2}", due)));
+                row.push(Cell::new(format!("${:.2}", position.market_value() - due)));
+
+                let mut net_flows = flows.clone();
+                net_flows.push((as_of, -due));
+                after_tax_flows.extend(net_flows);
+                total_tax += due;
+            }
+
+            positions_table.add_row(row);
+            all_flows.extend(flows);
+        }
+
+        let cost_basis: f32 = self
+            .positions
+            .iter()
+            .map(|position| position.cost_basis())
+            .sum();
+        let intrinsic_value: f32 = self
+            .positions
+            .iter()
+            .filter_map(|position| {
+                position
+                    .intrinsic
+                    .map(|intrinsic| intrinsic * position.shares())
+            })
+            .sum();
+        let valued: f32 = self
+            .positions
+            .iter()
+            .filter(|position| position.intrinsic.is_some())
+            .map(|position| position.market_value())
+            .sum();
+
+        // Table 2: TOTAL
+        let mut total_table = new_table(vec!["Portfolio", "Value"], vec![16, 16]);
+
+        total_table.add_row(vec![
+            Cell::new("Cost Basis"),
+            Cell::new(format!("${:.2}", cost_basis)),
+        ]);
+        total_table.add_row(vec![
+            Cell::new("Market Value"),
+            Cell::new(format!("${:.2}", total_value)),
+        ]);
+        total_table.add_row(vec![
+            Cell::new("Gain"),
+            Cell::new(format!("{:+.1}%", (total_value / cost_basis - 1.0) * 100.0)),
+        ]);
+        total_table.add_row(vec![
+            Cell::new("XIRR"),
+            Cell::new(match flows_xirr(&all_flows) {
+                Some(rate) => format!("{:.1}%", rate * 100.0),
+                None => "n/a".to_string(),
+            }),
+        ]);
+
+        if self.tax.is_some() {
+            total_table.add_row(vec![
+                Cell::new("Tax on Sale"),
+                Cell::new(format!("${:.2}", total_tax)),
+            ]);
+            total_table.add_row(vec![
+                Cell::new("After Tax"),
+                Cell::new(format!("${:.2}", total_value - total_tax)),
+            ]);
+            total_table.add_row(vec![
+                Cell::new("After-Tax XIRR"),
+                Cell::new(match flows_xirr(&after_tax_flows) {
+                    Some(rate) => format!("{:.1}%", rate * 100.0),
+                    None => "n/a".to_string(),
+                }),
+            ]);
+        }
+
+        if valued > 0.0 {
+            total_table.add_row(vec![
+                Cell::new("Upside"),
+                Cell::new(format!("{:+.1}%", (intrinsic_value / valued - 1.0) * 100.0)),
+            ]);
+        }
+
+        if targets.iter().any(|target| target.is_some()) {
+            let invested: f32 = targets.iter().flatten().sum();
+            total_table.add_row(vec![
+                Cell::new("Kelly Fraction"),
+                Cell::new(format!("{:.2}", self.kelly_fraction)),
+            ]);
+            total_table.add_row(vec![
+                Cell::new("Kelly Invested"),
+                Cell::new(format!("{:.1}%", invested * 100.0)),
+            ]);
+        }
+
+        print_side_by_side(&[&positions_table]);
+        println!("{}", total_table);
+        println!();
+
+        total_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(ticker: &str, price: f32, scenarios: Vec<(f32, f32)>) -> Position {
+        Position {
+            ticker: ticker.to_string(),
+            price,
+            intrinsic: None,
+            scenarios,
+            lots: vec![Lot {
+                date: parse_date("2024-01-01").unwrap(),
+                shares: 10.0,
+                cost: 100.0,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_position_xirr_of_a_single_lot() {
+        let flows = position("AAA", 121.0, vec![]).flows(parse_date("2026-01-01").unwrap());
+
+        // 731 days held, slightly above two years of 365.25 days
+        assert_eq!(flows_xirr(&flows), Some(0.09992829));
+    }
+
+    #[test]
+    fn test_kelly_targets_use_the_kelly_fraction() {
+        let portfolio = PortfolioBuilder::new()
+            .add_positions(vec![
+                position("AAA", 100.0, vec![(0.8, 1.0), (0.2, -1.0)]),
+                position("BBB", 100.0, vec![(0.75, 1.0), (0.25, -1.0)]),
+                position("CCC", 100.0, vec![]),
+            ])
+            .add_kelly_fraction(0.5);
+
+        assert_eq!(portfolio.targets(), vec![Some(0.3), Some(0.25), None]);
+    }
+
+    #[test]
+    fn test_capital_gains_tax_of_the_lots() {
+        let mut position = position("AAA", 120.0, vec![]);
+        position.lots.push(Lot {
+            date: parse_date("2025-06-01").unwrap(),
+            shares: 10.0,
+            cost: 125.0,
+        });
+        let tax = TaxRates::new()
+            .add_capital_gains(0.15)
+            .add_short_term(Some(0.4));
+
+        // 200 of long term gain less 50 of short term loss, taxed at the long term rate
+        assert_eq!(
+            position.capital_gains_tax(&tax, parse_date("2025-12-01").unwrap()),
+            22.5
+        );
+    }
+}
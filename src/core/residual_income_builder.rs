@@ -0,0 +1,231 @@
+use super::growth_assumption_builder::GrowthAssumptionBuilder;
+use crate::utils::config::DEFAULT_DISCOUNT_RATE;
+use crate::utils::financial::{margin_of_safety, pv};
+use crate::utils::table::{new_table, print_side_by_side};
+use comfy_table::Cell;
+
+#[derive(PartialEq, Debug)]
+pub struct ResidualIncomeBuilder {
+    // current book value per share
+    book_value: Option<f32>,
+    // cost of equity
+    rate: Option<f32>,
+    // return on equity of the first projected year, fades toward the cost of equity
+    roe: Option<f32>,
+    // book value growth, one assumption per projected year
+    growth_assumptions: GrowthAssumptionBuilder,
+    // optional market price of one share
+    share_price: Option<f32>,
+}
+
+impl ResidualIncomeBuilder {
+    pub fn new() -> ResidualIncomeBuilder {
+        ResidualIncomeBuilder {
+            book_value: None,
+            rate: Some(DEFAULT_DISCOUNT_RATE),
+            roe: None,
+            growth_assumptions: GrowthAssumptionBuilder::new(),
+            share_price: None,
+        }
+    }
+
+    pub fn add_book_value(mut self, book_value: f32) -> ResidualIncomeBuilder {
+        self.book_value = Some(book_value);
+
+        self
+    }
+
+    pub fn add_rate(mut self, rate: f32) -> ResidualIncomeBuilder {
+        self.rate = Some(rate);
+
+        self
+    }
+
+    pub fn add_roe(mut self, roe: f32) -> ResidualIncomeBuilder {
+        self.roe = Some(roe);
+
+        self
+    }
+
+    pub fn add_growth_assumptions(
+        mut self,
+        growths: GrowthAssumptionBuilder,
+    ) -> ResidualIncomeBuilder {
+        self.growth_assumptions = growths;
+
+        self
+    }
+
+    pub fn add_share_price(mut self, share_price: Option<f32>) -> ResidualIncomeBuilder {
+        self.share_price = share_price;
+
+        self
+    }
+
+    /// ROE of a projected year, starting at the initial ROE and fading linearly to meet
+    /// the cost of equity the year after the projection, so the last year still earns a spread
+    pub fn roe_at(&self, year: i32) -> f32 {
+        let rate = self.rate.unwrap();
+        let roe = self.roe.unwrap();
+        let horizon = self.growth_assumptions.assumptions.len();
+
+        roe + (rate - roe) * (year - 1) as f32 / horizon as f32
+    }
+
+    pub fn execute(self) -> f32 {
+        let initial_book_value = self.book_value.unwrap();
+        let mut book_value = initial_book_value;
+        let rate = self.rate.unwrap();
+        let roe = self.roe.unwrap();
+        let growth_assumptions = &self.growth_assumptions.assumptions;
+
+        // Table 1: ASSUMPTIONS
+        let mut assumptions_table = new_table(vec!["Assumptions", "Value"], vec![16, 16]);
+
+        assumptions_table.add_row(vec![
+            Cell::new("Book Value"),
+            Cell::new(format!("${:.2}", book_value)),
+        ]);
+        assumptions_table.add_row(vec![
+            Cell::new("Cost of Equity"),
+            Cell::new(format!("{:.1}%", rate * 100.0)),
+        ]);
+        assumptions_table.add_row(vec![
+            Cell::new("Initial ROE"),
+            Cell::new(format!("{:.1}%", roe * 100.0)),
+        ]);
+
+        let growth_years: Vec<String> = growth_assumptions
+            .iter()
+            .enumerate()
+            .map(|(i, &g)| format!("Year {}: {:.1}%", i + 1, g * 100.0))
+            .collect();
+        assumptions_table.add_row(vec![
+            Cell::new("Book Growth"),
+            Cell::new(growth_years.join("\n")),
+        ]);
+
+        if let Some(price) = self.share_price {
+            assumptions_table.add_row(vec![
+                Cell::new("Share Price"),
+                Cell::new(format!("${:.2}", price)),
+            ]);
+        }
+
+        // Table 2: RESIDUAL INCOME PROJECTIONS
+        // RI is earned on the book value at the start of the year
+        let mut residual_table = new_table(
+            vec!["Yr", "Open BV", "ROE", "RI", "PV"],
+            vec![4, 9, 7, 6, 6],
+        );
+
+        let mut residual_pv = 0.0;
+
+        for (index, assumption_rate) in growth_assumptions.iter().enumerate() {
+            let year = index as i32 + 1;
+            let year_roe = self.roe_at(year);
+            let residual_income = (year_roe - rate) * book_value;
+            let pv_value = pv(rate, year, residual_income);
+
+            residual_table.add_row(vec![
+                Cell::new(year),
+                Cell::new(format!("{:.2}", book_value)),
+                Cell::new(format!("{:.1}%", year_roe * 100.0)),
+                Cell::new(format!("{:.2}", residual_income)),
+                Cell::new(format!("{:.2}", pv_value)),
+            ]);
+            residual_pv += pv_value;
+            book_value *= 1. + assumption_rate;
+        }
+
+        let result = initial_book_value + residual_pv;
+
+        // Table 3: VALUATION
+        let mut valuation_table = new_table(vec!["Valuation", "Value"], vec![16, 16]);
+
+        valuation_table.add_row(vec![
+            Cell::new("Book Value"),
+            Cell::new(format!("${:.2}", initial_book_value)),
+        ]);
+        valuation_table.add_row(vec![
+            Cell::new("+ PV Residual"),
+            Cell::new(format!("${:.2}", residual_pv)),
+        ]);
+        valuation_table.add_row(vec![
+            Cell::new("= per Share"),
+            Cell::new(format!("${:.2}", result)),
+        ]);
+        valuation_table.add_row(vec![
+            Cell::new("P/B Justified"),
+            Cell::new(format!("{:.2}x", result / initial_book_value)),
+        ]);
+
+        if let Some(price) = self.share_price {
+            valuation_table.add_row(vec![Cell::new("")]);
+            valuation_table.add_row(vec![
+                Cell::new("Price"),
+                Cell::new(format!("${:.2}", price)),
+            ]);
+            valuation_table.add_row(vec![
+                Cell::new("Margin of Safety"),
+                Cell::new(match margin_of_safety(result, price) {
+                    Some(margin) => format!("{:.1}%", margin * 100.0),
+                    None => "n/a".to_string(),
+                }),
+            ]);
+        }
+
+        print_side_by_side(&[&assumptions_table, &residual_table, &valuation_table]);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::growth_assumption_builder::GrowthAssumption;
+
+    #[test]
+    fn test_residual_income_fades_toward_the_cost_of_equity() {
+        let residual = ResidualIncomeBuilder::new()
+            .add_book_value(20.0)
+            .add_rate(0.10)
+            .add_roe(0.15)
+            .add_growth_assumptions(
+                GrowthAssumptionBuilder::new().add(GrowthAssumption(2, 0.05, None)),
+            );
+
+        // year 1: (15% - 10%) * 20, year 2: (12.5% - 10%) * 21
+        assert_eq!(residual.execute(), 21.342976);
+    }
+
+    #[test]
+    fn test_roe_fade_keeps_a_spread_on_the_last_year() {
+        let residual = ResidualIncomeBuilder::new()
+            .add_book_value(20.0)
+            .add_rate(0.10)
+            .add_roe(0.20)
+            .add_growth_assumptions(
+                GrowthAssumptionBuilder::new().add(GrowthAssumption(4, 0.0, None)),
+            );
+
+        assert_eq!(residual.roe_at(1), 0.2);
+        assert_eq!(residual.roe_at(4), 0.125);
+        assert_eq!(residual.roe_at(5), 0.1);
+    }
+
+    #[test]
+    fn test_roe_below_the_cost_of_equity_destroys_value() {
+        let residual = ResidualIncomeBuilder::new()
+            .add_book_value(20.0)
+            .add_rate(0.10)
+            .add_roe(0.06)
+            .add_growth_assumptions(
+                GrowthAssumptionBuilder::new().add(GrowthAssumption(2, 0.0, None)),
+            );
+
+        // year 1: (6% - 10%) * 20, year 2: (8% - 10%) * 20, worth less than its book value
+        assert_eq!(residual.execute(), 18.942148);
+    }
+}
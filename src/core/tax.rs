@@ -0,0 +1,163 @@
+use crate::utils::financial::cagr;
+
+/// Tax rates of a taxable account
+#[derive(PartialEq, Debug, Clone)]
+pub struct TaxRates {
+    // rate of the gains held at least `long_term_days`
+    capital_gains: f32,
+    // optional rate of the gains held less, the long term one by default
+    short_term: Option<f32>,
+    // rate of the dividends, paid every year
+    dividend: f32,
+    // holding period from which a gain is taxed at the long term rate
+    long_term_days: i64,
+}
+
+impl TaxRates {
+    pub fn new() -> TaxRates {
+        TaxRates {
+            capital_gains: 0.0,
+            short_term: None,
+            dividend: 0.0,
+            long_term_days: 365,
+        }
+    }
+
+    pub fn add_capital_gains(mut self, capital_gains: f32) -> TaxRates {
+        self.capital_gains = capital_gains;
+
+        self
+    }
+
+    pub fn add_short_term(mut self, short_term: Option<f32>) -> TaxRates {
+        self.short_term = short_term;
+
+        self
+    }
+
+    pub fn add_dividend(mut self, dividend: f32) -> TaxRates {
+        self.dividend = dividend;
+
+        self
+    }
+
+    pub fn add_long_term_days(mut self, long_term_days: i64) -> TaxRates {
+        self.long_term_days = long_term_days;
+
+        self
+    }
+
+    /// Capital gains rate of a position held `held_days`
+    pub fn capital_gains_rate(&self, held_days: i64) -> f32 {
+        if held_days >= self.long_term_days {
+            self.capital_gains
+        } else {
+            self.short_term.unwrap_or(self.capital_gains)
+        }
+    }
+
+    /// Tax due on selling with a gain, losses are not taxed
+    pub fn capital_gains_tax(&self, gain: f32, held_days: i64) -> f32 {
+        gain.max(0.0) * self.capital_gains_rate(held_days)
+    }
+
+    /// Tax due on selling several lots: (gain, held days). The gains are netted within each
+    /// holding period first, then a net loss of one period offsets the gains of the other
+    pub fn netted_capital_gains_tax(&self, gains: &[(f32, i64)]) -> f32 {
+        let (long_term, short_term): (Vec<_>, Vec<_>) = gains
+            .iter()
+            .partition(|(_, held_days)| *held_days >= self.long_term_days);
+        let long_term: f32 = long_term.iter().map(|(gain, _)| gain).sum();
+        let short_term: f32 = short_term.iter().map(|(gain, _)| gain).sum();
+
+        let (long_term, short_term) = if short_term < 0.0 {
+            (long_term + short_term, 0.0)
+        } else if long_term < 0.0 {
+            (0.0, short_term + long_term)
+        } else {
+            (long_term, short_term)
+        };
+
+        self.capital_gains_tax(long_term, self.long_term_days)
+            + self.capital_gains_tax(short_term, 0)
+    }
+
+    /// Value and cost basis after `periods` years of price growth, the dividends
+    /// being taxed and reinvested every year
+    pub fn compound(
+        &self,
+        initial_value: f32,
+        price_growth: f32,
+        dividend_yield: f32,
+        periods: i32,
+    ) -> (f32, f32) {
+        let mut value = initial_value;
+        let mut basis = initial_value;
+
+        for _ in 0..periods {
+            value *= 1. + price_growth;
+            let reinvested = value * dividend_yield * (1. - self.dividend);
+            value += reinvested;
+            // taxed dividends are bought at their value and add to the cost basis
+            basis += reinvested;
+        }
+
+        (value, basis)
+    }
+
+    /// CAGR of the proceeds of selling after `periods` years, net of dividend and capital gains taxes
+    pub fn after_tax_cagr(
+        &self,
+        final_value: f32,
+        initial_value: f32,
+        periods: i32,
+        dividend_yield: f32,
+    ) -> f32 {
+        let price_growth = cagr(final_value, initial_value, periods);
+        let (value, basis) = self.compound(initial_value, price_growth, dividend_yield, periods);
+        let proceeds = value - self.capital_gains_tax(value - basis, periods as i64 * 365);
+
+        cagr(proceeds, initial_value, periods)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_term_rate_below_the_holding_period() {
+        let tax = TaxRates::new()
+            .add_capital_gains(0.15)
+            .add_short_term(Some(0.35));
+
+        assert_eq!(tax.capital_gains_tax(100.0, 364), 35.0);
+        assert_eq!(tax.capital_gains_tax(100.0, 365), 15.000001);
+        assert_eq!(tax.capital_gains_tax(-100.0, 365), 0.0);
+    }
+
+    #[test]
+    fn test_short_term_loss_offsets_the_long_term_gain_before_the_rate() {
+        let tax = TaxRates::new()
+            .add_capital_gains(0.15)
+            .add_short_term(Some(0.35));
+
+        // 100 of long term gain less 60 of short term loss, 40 taxed at the long term rate
+        assert_eq!(
+            tax.netted_capital_gains_tax(&[(100.0, 400), (-60.0, 30)]),
+            6.0
+        );
+        assert_eq!(
+            tax.netted_capital_gains_tax(&[(100.0, 400), (-160.0, 30)]),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_after_tax_cagr_of_a_gain_taxed_on_exit() {
+        let tax = TaxRates::new().add_capital_gains(0.2);
+
+        // 100 -> 200, 80 of gain kept after tax
+        assert_eq!(tax.after_tax_cagr(200.0, 100.0, 1, 0.0), 0.79999995);
+    }
+}
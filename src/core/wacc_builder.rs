@@ -0,0 +1,302 @@
+use crate::utils::grammar::{parse_number, parse_rate, parse_weight};
+use crate::utils::table::new_table;
+use comfy_table::Cell;
+use std::str::FromStr;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct WaccBuilder {
+    risk_free_rate: f32,
+    equity_risk_premium: f32,
+    // optional beta, without it the cost of equity is built up from premiums
+    beta: Option<f32>,
+    // optional small company premium for the build-up method
+    size_premium: Option<f32>,
+    // optional company specific risk premium for the build-up method
+    specific_risk_premium: Option<f32>,
+    // pre-tax cost of debt
+    cost_of_debt: f32,
+    tax_rate: f32,
+    // share of debt in the capital structure, the rest is equity
+    debt_weight: f32,
+}
+
+impl WaccBuilder {
+    pub fn new() -> WaccBuilder {
+        WaccBuilder {
+            risk_free_rate: 0.0,
+            equity_risk_premium: 0.0,
+            beta: None,
+            size_premium: None,
+            specific_risk_premium: None,
+            cost_of_debt: 0.0,
+            tax_rate: 0.0,
+            debt_weight: 0.0,
+        }
+    }
+
+    pub fn add_risk_free_rate(mut self, risk_free_rate: f32) -> WaccBuilder {
+        self.risk_free_rate = risk_free_rate;
+
+        self
+    }
+
+    pub fn add_equity_risk_premium(mut self, equity_risk_premium: f32) -> WaccBuilder {
+        self.equity_risk_premium = equity_risk_premium;
+
+        self
+    }
+
+    pub fn add_beta(mut self, beta: Option<f32>) -> WaccBuilder {
+        self.beta = beta;
+
+        self
+    }
+
+    pub fn add_size_premium(mut self, size_premium: Option<f32>) -> WaccBuilder {
+        self.size_premium = size_premium;
+
+        self
+    }
+
+    pub fn add_specific_risk_premium(mut self, specific_risk_premium: Option<f32>) -> WaccBuilder {
+        self.specific_risk_premium = specific_risk_premium;
+
+        self
+    }
+
+    pub fn add_cost_of_debt(mut self, cost_of_debt: f32) -> WaccBuilder {
+        self.cost_of_debt = cost_of_debt;
+
+        self
+    }
+
+    pub fn add_tax_rate(mut self, tax_rate: f32) -> WaccBuilder {
+        self.tax_rate = tax_rate;
+
+        self
+    }
+
+    pub fn add_debt_weight(mut self, debt_weight: f32) -> WaccBuilder {
+        self.debt_weight = debt_weight;
+
+        self
+    }
+
+    /// CAPM when a beta is given, build-up method otherwise
+    pub fn cost_of_equity(&self) -> f32 {
+        self.risk_free_rate
+            + self.beta.unwrap_or(1.0) * self.equity_risk_premium
+            + self.size_premium.unwrap_or(0.0)
+            + self.specific_risk_premium.unwrap_or(0.0)
+    }
+
+    pub fn after_tax_cost_of_debt(&self) -> f32 {
+        self.cost_of_debt * (1.0 - self.tax_rate)
+    }
+
+    pub fn compute(&self) -> f32 {
+        (1.0 - self.debt_weight) * self.cost_of_equity()
+            + self.debt_weight * self.after_tax_cost_of_debt()
+    }
+
+    pub fn execute(self) -> f32 {
+        let wacc = self.compute();
+
+        let mut table = new_table(vec!["WACC", "Value"], vec![20, 12]);
+
+        table.add_row(vec![
+            Cell::new("Risk-free Rate"),
+            Cell::new(format!("{:.2}%", self.risk_free_rate * 100.0)),
+        ]);
+
+        match self.beta {
+            Some(beta) => {
+                table.add_row(vec![Cell::new("Beta"), Cell::new(format!("{:.2}", beta))]);
+                table.add_row(vec![
+                    Cell::new("× Equity Premium"),
+                    Cell::new(format!("{:.2}%", self.equity_risk_premium * 100.0)),
+                ]);
+            }
+            None => {
+                table.add_row(vec![
+                    Cell::new("+ Equity Premium"),
+                    Cell::new(format!("{:.2}%", self.equity_risk_premium * 100.0)),
+                ]);
+            }
+        }
+
+        if let Some(size_premium) = self.size_premium {
+            table.add_row(vec![
+                Cell::new("+ Size Premium"),
+                Cell::new(format!("{:.2}%", size_premium * 100.0)),
+            ]);
+        }
+
+        if let Some(specific_risk_premium) = self.specific_risk_premium {
+            table.add_row(vec![
+                Cell::new("+ Specific Premium"),
+                Cell::new(format!("{:.2}%", specific_risk_premium * 100.0)),
+            ]);
+        }
+
+        table.add_row(vec![
+            Cell::new("= Cost of Equity"),
+            Cell::new(format!("{:.2}%", self.cost_of_equity() * 100.0)),
+        ]);
+        table.add_row(vec![
+            Cell::new("Cost of Debt"),
+            Cell::new(format!("{:.2}%", self.cost_of_debt * 100.0)),
+        ]);
+        table.add_row(vec![
+            Cell::new("Tax Rate"),
+            Cell::new(format!("{:.2}%", self.tax_rate * 100.0)),
+        ]);
+        table.add_row(vec![
+            Cell::new("= After-tax Debt"),
+            Cell::new(format!("{:.2}%", self.after_tax_cost_of_debt() * 100.0)),
+        ]);
+        table.add_row(vec![
+            Cell::new("Equity Weight"),
+            Cell::new(format!("{:.1}%", (1.0 - self.debt_weight) * 100.0)),
+        ]);
+        table.add_row(vec![
+            Cell::new("Debt Weight"),
+            Cell::new(format!("{:.1}%", self.debt_weight * 100.0)),
+        ]);
+        table.add_row(vec![
+            Cell::new("= WACC"),
+            Cell::new(format!("{:.2}%", wacc * 100.0)),
+        ]);
+
+        println!();
+        println!("{}", table);
+
+        wacc
+    }
+}
+
+/// Discount rate given either as a number or as `wacc:key=value,...`
+/// with the keys rf, erp, beta, size, specific, kd, tax and wd
+#[derive(Debug, Clone)]
+pub enum DiscountRate {
+    Fixed(f32),
+    Wacc(WaccBuilder),
+}
+
+impl DiscountRate {
+    /// Rate to discount at, without printing the WACC table
+    pub fn resolve(&self) -> f32 {
+        match self {
+            DiscountRate::Fixed(rate) => *rate,
+            DiscountRate::Wacc(wacc) => wacc.compute(),
+        }
+    }
+}
+
+impl FromStr for DiscountRate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(spec) = s.strip_prefix("wacc:") else {
+            return parse_rate(s)
+                .map(DiscountRate::Fixed)
+                .map_err(|error| format!("{}, or a wacc:... spec", error));
+        };
+
+        let mut wacc = WaccBuilder::new();
+        let (mut has_risk_free_rate, mut has_equity_risk_premium) = (false, false);
+
+        for pair in spec.split(',') {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value in wacc spec, got `{}`", pair))?;
+            let key = key.trim();
+            let invalid = |error: String| format!("invalid `{}` in wacc spec, {}", key, error);
+
+            wacc = match key {
+                "rf" => {
+                    has_risk_free_rate = true;
+                    wacc.add_risk_free_rate(parse_rate(value).map_err(invalid)?)
+                }
+                "erp" => {
+                    has_equity_risk_premium = true;
+                    wacc.add_equity_risk_premium(parse_rate(value).map_err(invalid)?)
+                }
+                "beta" => wacc.add_beta(Some(parse_number(value).map_err(invalid)?)),
+                "size" => wacc.add_size_premium(Some(parse_rate(value).map_err(invalid)?)),
+                "specific" => {
+                    wacc.add_specific_risk_premium(Some(parse_rate(value).map_err(invalid)?))
+                }
+                "kd" => wacc.add_cost_of_debt(parse_rate(value).map_err(invalid)?),
+                "tax" => wacc.add_tax_rate(parse_rate(value).map_err(invalid)?),
+                "wd" => wacc.add_debt_weight(parse_weight(value).map_err(invalid)?),
+                other => return Err(format!("unknown wacc key `{}`", other)),
+            };
+        }
+
+        if !has_risk_free_rate || !has_equity_risk_premium {
+            return Err(
+                "a wacc spec needs at least rf and erp, ex: wacc:rf=4%,erp=5.5%".to_string(),
+            );
+        }
+
+        Ok(DiscountRate::Wacc(wacc))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wacc_with_capm_cost_of_equity() {
+        let wacc = WaccBuilder::new()
+            .add_risk_free_rate(0.04)
+            .add_equity_risk_premium(0.05)
+            .add_beta(Some(1.2))
+            .add_cost_of_debt(0.06)
+            .add_tax_rate(0.25)
+            .add_debt_weight(0.3);
+
+        assert_eq!(wacc.cost_of_equity(), 0.1);
+        assert_eq!(wacc.compute(), 0.0835);
+    }
+
+    #[test]
+    fn test_build_up_cost_of_equity_without_beta() {
+        let wacc = WaccBuilder::new()
+            .add_risk_free_rate(0.04)
+            .add_equity_risk_premium(0.05)
+            .add_size_premium(Some(0.03))
+            .add_specific_risk_premium(Some(0.02));
+
+        assert_eq!(wacc.compute(), 0.14);
+    }
+
+    #[test]
+    fn test_discount_rate_from_a_number_or_a_wacc_spec() {
+        assert_eq!("12%".parse::<DiscountRate>().unwrap().resolve(), 0.12);
+        assert_eq!(
+            "wacc:rf=0.04,erp=0.05,beta=1.2,kd=0.06,tax=0.25,wd=0.3"
+                .parse::<DiscountRate>()
+                .unwrap()
+                .resolve(),
+            0.0835
+        );
+        assert_eq!(
+            "wacc:rf=4%,erp=5%,beta=1.2,kd=6%,tax=25%,wd=30%"
+                .parse::<DiscountRate>()
+                .unwrap()
+                .resolve(),
+            0.0835
+        );
+        assert_eq!(
+            "wacc:rf=0.04,erp=0.05,wd=30"
+                .parse::<DiscountRate>()
+                .unwrap_err(),
+            "invalid `wd` in wacc spec, the weight `30` must be between 0 and 1"
+        );
+        assert!("wacc:beta=1.1".parse::<DiscountRate>().is_err());
+        assert!("wacc:rf".parse::<DiscountRate>().is_err());
+    }
+}
@@ -0,0 +1,228 @@
+use crate::utils::table::{new_table, print_side_by_side};
+use comfy_table::Cell;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Ticker followed by the watchlist
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct WatchItem {
+    pub ticker: String,
+    // discount to the intrinsic value required before buying
+    pub margin_of_safety: f32,
+    // optional value per share, used instead of the latest journal run
+    pub value: Option<f32>,
+}
+
+/// Current market price of a ticker
+#[derive(PartialEq, Debug, Clone, Deserialize)]
+pub struct Quote {
+    pub ticker: String,
+    pub price: f32,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct ScreenRow {
+    pub ticker: String,
+    pub value: f32,
+    pub margin_of_safety: f32,
+    pub buy_price: f32,
+    pub price: f32,
+    // how far the price is below the buy price, negative when above
+    pub discount: f32,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct WatchlistBuilder {
+    items: Vec<WatchItem>,
+    // per share value of the latest journal run of each ticker
+    values: HashMap<String, f32>,
+    quotes: Vec<Quote>,
+    // also report the names trading above their buy price
+    all: bool,
+}
+
+impl WatchlistBuilder {
+    pub fn new() -> WatchlistBuilder {
+        WatchlistBuilder {
+            items: vec![],
+            values: HashMap::new(),
+            quotes: vec![],
+            all: false,
+        }
+    }
+
+    pub fn add_items(mut self, items: Vec<WatchItem>) -> WatchlistBuilder {
+        self.items = items;
+
+        self
+    }
+
+    pub fn add_values(mut self, values: HashMap<String, f32>) -> WatchlistBuilder {
+        self.values = values;
+
+        self
+    }
+
+    pub fn add_quotes(mut self, quotes: Vec<Quote>) -> WatchlistBuilder {
+        self.quotes = quotes;
+
+        self
+    }
+
+    pub fn add_all(mut self, all: bool) -> WatchlistBuilder {
+        self.all = all;
+
+        self
+    }
+
+    fn value(&self, item: &WatchItem) -> Option<f32> {
+        item.value
+            .or_else(|| self.values.get(&item.ticker).copied())
+    }
+
+    /// Tickers with a positive value and a price, the cheapest relative to their buy price first
+    pub fn screen(&self) -> Vec<ScreenRow> {
+        let mut rows: Vec<ScreenRow> = self
+            .items
+            .iter()
+            .filter_map(|item| {
+                let value = self.value(item).filter(|value| *value > 0.0)?;
+                let quote = self
+                    .quotes
+                    .iter()
+                    .find(|quote| quote.ticker.eq_ignore_ascii_case(&item.ticker))?;
+                let buy_price = value * (1.0 - item.margin_of_safety);
+
+                Some(ScreenRow {
+                    ticker: item.ticker.clone(),
+                    value,
+                    margin_of_safety: item.margin_of_safety,
+                    buy_price,
+                    price: quote.price,
+                    discount: 1.0 - quote.price / buy_price,
+                })
+            })
+            .filter(|row| self.all || row.discount >= 0.0)
+            .collect();
+
+        rows.sort_by(|a, b| b.discount.total_cmp(&a.discount));
+
+        rows
+    }
+
+    /// Prints the screen and returns the number of names below their buy price
+    pub fn execute(self) -> usize {
+        let rows = self.screen();
+
+        let mut table = new_table(
+            vec!["Ticker", "Value", "MoS", "Buy Below", "Price", "Discount"],
+            vec![8, 10, 7, 11, 10, 10],
+        );
+
+        for row in rows.iter() {
+            table.add_row(vec![
+                Cell::new(&row.ticker),
+                Cell::new(format!("${:.2}", row.value)),
+                Cell::new(format!("{:.0}%", row.margin_of_safety * 100.0)),
+                Cell::new(format!("${:.2}", row.buy_price)),
+                Cell::new(format!("${:.2}", row.price)),
+                Cell::new(format!("{:+.1}%", row.discount * 100.0)),
+            ]);
+        }
+
+        print_side_by_side(&[&table]);
+
+        let missing: Vec<&str> = self
+            .items
+            .iter()
+            .filter(|item| self.value(item).is_none())
+            .map(|item| item.ticker.as_str())
+            .collect();
+        if !missing.is_empty() {
+            println!("No per share value saved for: {}", missing.join(", "));
+        }
+
+        // a buy price below zero would rank the worst names first
+        let no_value: Vec<&str> = self
+            .items
+            .iter()
+            .filter(|item| self.value(item).is_some_and(|value| value <= 0.0))
+            .map(|item| item.ticker.as_str())
+            .collect();
+        if !no_value.is_empty() {
+            println!("No positive per share value for: {}", no_value.join(", "));
+        }
+
+        rows.iter().filter(|row| row.discount >= 0.0).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(ticker: &str, value: Option<f32>) -> WatchItem {
+        WatchItem {
+            ticker: ticker.to_string(),
+            margin_of_safety: 0.2,
+            value,
+        }
+    }
+
+    fn quote(ticker: &str, price: f32) -> Quote {
+        Quote {
+            ticker: ticker.to_string(),
+            price,
+        }
+    }
+
+    #[test]
+    fn test_screen_keeps_names_below_their_buy_price_sorted_by_discount() {
+        let watchlist = WatchlistBuilder::new()
+            .add_items(vec![
+                item("AAA", Some(100.0)),
+                item("BBB", None),
+                item("CCC", Some(50.0)),
+            ])
+            .add_values(HashMap::from([("BBB".to_string(), 200.0)]))
+            .add_quotes(vec![
+                quote("aaa", 72.0),
+                quote("BBB", 80.0),
+                quote("CCC", 45.0),
+            ]);
+
+        let tickers: Vec<String> = watchlist
+            .screen()
+            .into_iter()
+            .map(|row| row.ticker)
+            .collect();
+
+        // buy prices are 80, 160 and 40
+        assert_eq!(tickers, vec!["BBB", "AAA"]);
+    }
+
+    #[test]
+    fn test_discount_is_measured_against_the_buy_price() {
+        let watchlist = WatchlistBuilder::new()
+            .add_items(vec![item("AAA", Some(100.0))])
+            .add_quotes(vec![quote("AAA", 60.0)]);
+
+        assert_eq!(watchlist.screen()[0].discount, 0.25);
+    }
+
+    #[test]
+    fn test_screen_leaves_out_names_without_a_positive_value() {
+        let watchlist = WatchlistBuilder::new()
+            .add_items(vec![item("AAA", Some(-10.0)), item("BBB", Some(100.0))])
+            .add_quotes(vec![quote("AAA", 5.0), quote("BBB", 60.0)])
+            .add_all(true);
+
+        let tickers: Vec<String> = watchlist
+            .screen()
+            .into_iter()
+            .map(|row| row.ticker)
+            .collect();
+
+        assert_eq!(tickers, vec!["BBB"]);
+    }
+}
@@ -4,9 +4,20 @@ mod utils;
 
 use clap::Parser;
 use cli::cagr_cli_impl::CagrCliImpl;
+use cli::completions_cli_impl::CompletionsCliImpl;
+use cli::comps_cli_impl::CompsCliImpl;
+use cli::ddm_cli_impl::DdmCliImpl;
+use cli::epv_cli_impl::EpvCliImpl;
+use cli::graham_cli_impl::GrahamCliImpl;
 use cli::intrinsic_cli_impl::IntrinsicCliImpl;
+use cli::journal_cli_impl::JournalCliImpl;
 use cli::kelly_cli_impl::KellyCliImpl;
+use cli::man_cli_impl::ManCliImpl;
 use cli::port::Run;
+use cli::portfolio_cli_impl::PortfolioCliImpl;
+use cli::residual_income_cli_impl::ResidualIncomeCliImpl;
+use cli::wacc_cli_impl::WaccCliImpl;
+use cli::watchlist_cli_impl::WatchlistCliImpl;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -16,8 +27,19 @@ use cli::port::Run;
 )]
 enum Dhandho {
     Kelly(KellyCliImpl),
-    Intrinsic(IntrinsicCliImpl),
+    Intrinsic(Box<IntrinsicCliImpl>),
     Cagr(CagrCliImpl),
+    Graham(GrahamCliImpl),
+    Wacc(WaccCliImpl),
+    Ddm(DdmCliImpl),
+    ResidualIncome(ResidualIncomeCliImpl),
+    Epv(EpvCliImpl),
+    Comps(CompsCliImpl),
+    Journal(JournalCliImpl),
+    Watchlist(WatchlistCliImpl),
+    Portfolio(PortfolioCliImpl),
+    Completions(CompletionsCliImpl),
+    Man(ManCliImpl),
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -25,6 +47,17 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Dhandho::Kelly(kelly) => kelly.run(),
         Dhandho::Intrinsic(intrinsic) => intrinsic.run(),
         Dhandho::Cagr(cagr) => cagr.run(),
+        Dhandho::Graham(graham) => graham.run(),
+        Dhandho::Wacc(wacc) => wacc.run(),
+        Dhandho::Ddm(ddm) => ddm.run(),
+        Dhandho::ResidualIncome(residual_income) => residual_income.run(),
+        Dhandho::Epv(epv) => epv.run(),
+        Dhandho::Comps(comps) => comps.run(),
+        Dhandho::Journal(journal) => journal.run(),
+        Dhandho::Watchlist(watchlist) => watchlist.run(),
+        Dhandho::Portfolio(portfolio) => portfolio.run(),
+        Dhandho::Completions(completions) => completions.run(),
+        Dhandho::Man(man) => man.run(),
     }
 
     Ok(())
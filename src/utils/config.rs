@@ -0,0 +1,96 @@
+use super::storage::config_path;
+use super::table::OutputFormat;
+use serde::Deserialize;
+use std::fs;
+
+/// Discount rate used when neither the command line nor the config file gives one
+pub const DEFAULT_DISCOUNT_RATE: f32 = 0.15;
+/// Margin of safety required before buying when none is given
+pub const DEFAULT_MARGIN_OF_SAFETY: f32 = 0.3;
+/// Fraction of the full kelly weight targeted when none is given
+pub const DEFAULT_KELLY_FRACTION: f32 = 1.0;
+
+/// Defaults of the user, the options given on the command line take precedence
+#[derive(PartialEq, Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Config {
+    // discount rate of intrinsic and cost of equity or capital of ddm, residual-income and epv
+    pub discount_rate: Option<f32>,
+    // margin of safety of the tickers added to the watchlist
+    pub margin_of_safety: Option<f32>,
+    // output of the reports with one row per item
+    pub format: Option<OutputFormat>,
+    // ISO code of the currency of the amounts
+    pub currency: Option<String>,
+    // fraction of the full kelly weight targeted by the portfolio
+    pub kelly_fraction: Option<f32>,
+    // decimals of the amounts
+    pub precision: Option<usize>,
+}
+
+impl Config {
+    /// Reads the config file, every default is unset when there is none
+    pub fn load() -> Result<Config, String> {
+        let path = config_path();
+        let Ok(content) = fs::read_to_string(&path) else {
+            return Ok(Config::default());
+        };
+
+        toml::from_str(&content)
+            .map_err(|error| format!("invalid config file {}: {}", path.display(), error))
+    }
+
+    pub fn discount_rate(&self) -> f32 {
+        self.discount_rate.unwrap_or(DEFAULT_DISCOUNT_RATE)
+    }
+
+    pub fn margin_of_safety(&self) -> f32 {
+        self.margin_of_safety.unwrap_or(DEFAULT_MARGIN_OF_SAFETY)
+    }
+
+    pub fn format(&self) -> OutputFormat {
+        self.format.unwrap_or(OutputFormat::Table)
+    }
+
+    pub fn kelly_fraction(&self) -> f32 {
+        self.kelly_fraction.unwrap_or(DEFAULT_KELLY_FRACTION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_file_with_house_defaults() {
+        let config: Config = toml::from_str(
+            r#"
+            discount-rate = 0.1
+            format = "csv"
+            currency = "EUR"
+            precision = 0
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.discount_rate(), 0.1);
+        assert_eq!(config.format(), OutputFormat::Csv);
+        assert_eq!(config.currency.as_deref(), Some("EUR"));
+        assert_eq!(config.precision, Some(0));
+        assert_eq!(config.kelly_fraction(), DEFAULT_KELLY_FRACTION);
+    }
+
+    #[test]
+    fn test_unknown_key_is_an_error() {
+        assert!(toml::from_str::<Config>("discount = 0.1").is_err());
+    }
+
+    #[test]
+    fn test_empty_config_uses_the_builtin_defaults() {
+        let config = Config::default();
+
+        assert_eq!(config.discount_rate(), DEFAULT_DISCOUNT_RATE);
+        assert_eq!(config.margin_of_safety(), DEFAULT_MARGIN_OF_SAFETY);
+        assert_eq!(config.format(), OutputFormat::Table);
+    }
+}
@@ -0,0 +1,115 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds since the unix epoch
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before 1970")
+        .as_secs()
+}
+
+/// (year, month, day) of the days elapsed since 1970-01-01, proleptic gregorian calendar
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // shift the epoch to 0000-03-01 so the leap day is the last day of the year
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+/// Days elapsed since 1970-01-01 of a proleptic gregorian date
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_index = if month > 2 { month - 3 } else { month + 9 } as i64;
+    let day_of_year = (153 * month_index + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Days since 1970-01-01 of a date in the format YYYY-MM-DD
+pub fn parse_date(date: &str) -> Result<i64, String> {
+    let invalid = || format!("invalid date `{}`, expected YYYY-MM-DD", date.trim());
+    let parts = date
+        .trim()
+        .split('-')
+        .map(|part| part.parse::<u32>().map_err(|_| invalid()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let [year, month, day] = parts[..] else {
+        return Err(invalid());
+    };
+
+    // out of range months and days would roll over into another date
+    let days = days_from_civil(year as i64, month, day);
+    if !(1..=12).contains(&month) || civil_from_days(days) != (year as i64, month, day) {
+        return Err(invalid());
+    }
+
+    Ok(days)
+}
+
+/// Formats a unix timestamp as YYYY-MM-DD HH:MM in UTC
+pub fn format_timestamp(timestamp: u64) -> String {
+    let seconds = timestamp as i64;
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let minutes = seconds.rem_euclid(86_400) / 60;
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}",
+        year,
+        month,
+        day,
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_from_days_handles_leap_days() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    }
+
+    #[test]
+    fn test_parse_date_is_the_inverse_of_civil_from_days() {
+        assert_eq!(parse_date("2000-02-29"), Ok(11_016));
+        assert_eq!(
+            civil_from_days(parse_date("2026-10-19").unwrap()),
+            (2026, 10, 19)
+        );
+    }
+
+    #[test]
+    fn test_parse_date_rejects_other_formats_and_invalid_days() {
+        assert_eq!(
+            parse_date("2024/01/01"),
+            Err("invalid date `2024/01/01`, expected YYYY-MM-DD".to_string())
+        );
+        assert!(parse_date("2023-02-29").is_err());
+        assert!(parse_date("2024-13-01").is_err());
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(1_792_418_580), "2026-10-19 14:03");
+    }
+}
@@ -8,6 +8,86 @@ pub fn cagr(final_value: f32, initial_value:f32, periods: i32) -> f32 {
     (final_value/initial_value).powf(1.0f32 / periods as f32) - 1.0
 }
 
+/// Real rate of a nominal one once inflation is removed, Fisher equation
+pub fn real_rate(nominal_rate: f32, inflation: f32) -> f32 {
+    (1. + nominal_rate) / (1. + inflation) - 1.
+}
+
+/// Nominal rate of a real one once inflation is added, Fisher equation
+pub fn nominal_rate(real_rate: f32, inflation: f32) -> f32 {
+    (1. + real_rate) * (1. + inflation) - 1.
+}
+
+/// Probability of surviving `periods` years with a constant annual hazard rate
+pub fn survival(hazard_rate: f32, periods: i32) -> f32 {
+    (1. - hazard_rate).powi(periods)
+}
+
+/// Discount rate earning `rate` on the cash flows that survive a constant annual hazard rate
+pub fn hazard_adjusted_rate(rate: f32, hazard_rate: f32) -> f32 {
+    (1. + rate) / (1. - hazard_rate) - 1.
+}
+
+/// Graham Number, the most a defensive investor should pay for a share,
+/// none for a loss or a negative book value
+pub fn graham_number(eps: f32, book_value_per_share: f32) -> Option<f32> {
+    if eps <= 0.0 || book_value_per_share <= 0.0 {
+        return None;
+    }
+
+    Some((22.5 * eps * book_value_per_share).sqrt())
+}
+
+/// Revised Graham formula, growth and bond yield given as fractions,
+/// none without a positive bond yield or when the formula gives no positive value
+pub fn graham_value(eps: f32, growth: f32, bond_yield: f32) -> Option<f32> {
+    if bond_yield <= 0.0 {
+        return None;
+    }
+
+    let value = eps * (8.5 + 2.0 * growth * 100.0) * 4.4 / (bond_yield * 100.0);
+
+    (value > 0.0).then_some(value)
+}
+
+/// Margin of Safety, the discount of the price to the intrinsic value,
+/// none when there is no value to discount
+pub fn margin_of_safety(intrinsic_value: f32, price: f32) -> Option<f32> {
+    if intrinsic_value <= 0.0 {
+        return None;
+    }
+
+    Some((intrinsic_value - price) / intrinsic_value)
+}
+
+/// Extended Internal Rate of Return of dated cash flows: (years since the first flow, amount),
+/// none when the flows never change sign
+pub fn xirr(flows: &[(f32, f32)]) -> Option<f32> {
+    let npv = |rate: f64| -> f64 {
+        flows
+            .iter()
+            .map(|&(years, amount)| amount as f64 / (1.0 + rate).powf(years as f64))
+            .sum()
+    };
+
+    // the npv decreases with the rate when the investments come before the returns
+    let (mut low, mut high) = (-0.9999, 100.0);
+    if npv(low).signum() == npv(high).signum() {
+        return None;
+    }
+
+    for _ in 0..200 {
+        let mid = (low + high) / 2.0;
+        if npv(mid).signum() == npv(low).signum() {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Some(((low + high) / 2.0) as f32)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -28,4 +108,41 @@ mod tests {
 
         assert_eq!(cagr(final_value, initial_value, 1), 0.1875)
     }
+
+    #[test]
+    fn test_fisher_equation() {
+        assert_eq!(real_rate(0.1, 0.05), 0.047619104);
+        assert_eq!(nominal_rate(0.05, 0.02), 0.07099998);
+    }
+
+    #[test]
+    fn test_hazard_adjusted_rate() {
+        // 1.1 / 0.9, earning 10% on the 90% of the value surviving the year
+        assert_eq!(hazard_adjusted_rate(0.1, 0.1), 0.22222233);
+    }
+
+    #[test]
+    fn test_margin_of_safety_needs_a_positive_value() {
+        assert_eq!(margin_of_safety(20.0, 15.0), Some(0.25));
+        assert_eq!(margin_of_safety(-8.98, 5.0), None);
+    }
+
+    #[test]
+    fn test_graham_number_formula() {
+        assert_eq!(graham_number(2.0, 20.0), Some(30.0));
+        assert_eq!(graham_number(-2.0, -20.0), None);
+    }
+
+    #[test]
+    fn test_graham_value_formula() {
+        assert_eq!(graham_value(2.0, 0.05, 0.044), Some(37.0));
+        assert_eq!(graham_value(2.0, -0.1, 0.044), None);
+        assert_eq!(graham_value(2.0, 0.05, 0.0), None);
+    }
+
+    #[test]
+    fn test_xirr_matches_the_cagr_of_a_single_investment() {
+        assert_eq!(xirr(&[(0.0, -100.0), (2.0, 121.0)]), Some(0.1));
+        assert_eq!(xirr(&[(0.0, 100.0), (1.0, 50.0)]), None);
+    }
 }
@@ -0,0 +1,116 @@
+/// Number of the assumption grammar: `12%` is 0.12, `+21x` is 21, `0.12` is 0.12
+pub fn parse_number(token: &str) -> Result<f32, String> {
+    let token = token.trim();
+    let (digits, divisor) = match token.strip_suffix('%') {
+        Some(digits) => (digits, 100.0),
+        None => (token.strip_suffix(['x', 'X']).unwrap_or(token), 1.0),
+    };
+
+    digits
+        .trim()
+        .trim_start_matches('+')
+        .parse::<f32>()
+        .map(|number| number / divisor)
+        .map_err(|_| format!("`{}` is not a number, ex: 12%, 0.12 or 21x", token))
+}
+
+/// Rate given as a percentage or a fraction, plain numbers from 1 up are taken for a forgotten `%`
+pub fn parse_rate(token: &str) -> Result<f32, String> {
+    let rate = parse_number(token)?;
+
+    if !token.trim().ends_with('%') && rate.abs() >= 1.0 {
+        return Err(format!(
+            "ambiguous rate `{}`, write {}% or {}",
+            token.trim(),
+            rate,
+            rate / 100.0
+        ));
+    }
+
+    Ok(rate)
+}
+
+/// Multiple above zero: `12x` or `12`
+pub fn parse_multiple(token: &str) -> Result<f32, String> {
+    let multiple = parse_number(token)?;
+
+    if multiple <= 0.0 {
+        return Err(format!("the multiple `{}` must be above zero", token.trim()));
+    }
+
+    Ok(multiple)
+}
+
+/// Share of a whole from 0 to 1: `30%` or `0.3`
+pub fn parse_weight(token: &str) -> Result<f32, String> {
+    let weight = parse_number(token)?;
+
+    if !(0.0..=1.0).contains(&weight) {
+        return Err(format!("the weight `{}` must be between 0 and 1", token.trim()));
+    }
+
+    Ok(weight)
+}
+
+/// Annual probability from 0 up to, but not including, 1: `2%` or `0.02`
+pub fn parse_probability(token: &str) -> Result<f32, String> {
+    let probability = parse_number(token)?;
+
+    if !(0.0..1.0).contains(&probability) {
+        return Err(format!(
+            "the probability `{}` must be at least 0 and below 1",
+            token.trim()
+        ));
+    }
+
+    Ok(probability)
+}
+
+/// Whole number of years: `5`, `5y` or `5 years`
+pub fn parse_years(token: &str) -> Result<u8, String> {
+    let token = token.trim();
+    let digits = ["years", "year", "yrs", "yr", "y"]
+        .iter()
+        .find_map(|suffix| token.strip_suffix(suffix))
+        .unwrap_or(token);
+
+    digits
+        .trim()
+        .parse::<u8>()
+        .map_err(|_| format!("`{}` is not a whole number of years, ex: 5y", token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers_with_units() {
+        assert_eq!(parse_number("12%"), Ok(0.12));
+        assert_eq!(parse_number("-1%"), Ok(-0.01));
+        assert_eq!(parse_number("+21x"), Ok(21.0));
+        assert_eq!(parse_number("0.05"), Ok(0.05));
+        assert_eq!(parse_years("5 years"), Ok(5));
+        assert_eq!(parse_multiple("12x"), Ok(12.0));
+        assert!(parse_multiple("0").is_err());
+        assert_eq!(parse_weight("30%"), Ok(0.3));
+        assert!(parse_weight("30").is_err());
+        assert_eq!(parse_probability("2%"), Ok(0.02));
+        assert!(parse_probability("100%").is_err());
+        assert!(parse_number("abc").is_err());
+    }
+
+    #[test]
+    fn test_plain_rate_from_one_is_ambiguous() {
+        assert_eq!(parse_rate("5%"), Ok(0.05));
+        assert_eq!(
+            parse_rate("5"),
+            Err("ambiguous rate `5`, write 5% or 0.05".to_string())
+        );
+        assert_eq!(
+            parse_rate("-1"),
+            Err("ambiguous rate `-1`, write -1% or -0.01".to_string())
+        );
+        assert_eq!(parse_rate("100%"), Ok(1.0));
+    }
+}
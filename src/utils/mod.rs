@@ -1 +1,8 @@
+pub mod config;
+pub mod date;
 pub mod financial;
+pub mod grammar;
+pub mod money;
+pub mod statistics;
+pub mod storage;
+pub mod table;
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Unit the amounts are expressed in
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Scale {
+    Units,
+    Thousands,
+    Millions,
+    Billions,
+}
+
+impl FromStr for Scale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "units" | "1" => Ok(Scale::Units),
+            "thousands" | "k" | "K" => Ok(Scale::Thousands),
+            "millions" | "m" | "M" => Ok(Scale::Millions),
+            "billions" | "b" | "B" => Ok(Scale::Billions),
+            _ => Err(format!(
+                "unknown scale `{}`, expected one of units, thousands, millions, billions",
+                s
+            )),
+        }
+    }
+}
+
+impl Scale {
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            Scale::Units => "",
+            Scale::Thousands => "K",
+            Scale::Millions => "M",
+            Scale::Billions => "B",
+        }
+    }
+}
+
+/// Symbol of an ISO 4217 currency code, the code itself when it has no common symbol
+pub fn symbol(currency: &str) -> String {
+    match currency.to_uppercase().as_str() {
+        "USD" => "$".to_string(),
+        "EUR" => "€".to_string(),
+        "GBP" => "£".to_string(),
+        "JPY" | "CNY" => "¥".to_string(),
+        "INR" => "₹".to_string(),
+        "KRW" => "₩".to_string(),
+        code => format!("{} ", code),
+    }
+}
+
+/// Formats amounts in a currency and a unit, dollars with no unit and two decimals by default
+#[derive(PartialEq, Debug, Clone)]
+pub struct Money {
+    symbol: String,
+    suffix: &'static str,
+    precision: usize,
+}
+
+impl Money {
+    pub fn new(currency: Option<&str>, scale: Option<Scale>) -> Money {
+        Money {
+            symbol: symbol(currency.unwrap_or("USD")),
+            suffix: scale.unwrap_or(Scale::Units).suffix(),
+            precision: 2,
+        }
+    }
+
+    pub fn add_precision(mut self, precision: usize) -> Money {
+        self.precision = precision;
+
+        self
+    }
+
+    pub fn format(&self, amount: f32) -> String {
+        format!(
+            "{}{:.*}{}",
+            self.symbol, self.precision, amount, self.suffix
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_money_is_dollars() {
+        assert_eq!(Money::new(None, None).format(9.8), "$9.80");
+    }
+
+    #[test]
+    fn test_money_with_currency_and_scale() {
+        assert_eq!(
+            Money::new(Some("eur"), Some(Scale::Billions)).format(9.8),
+            "€9.80B"
+        );
+        assert_eq!(Money::new(Some("CHF"), None).format(12.0), "CHF 12.00");
+        assert_eq!(Money::new(None, None).add_precision(0).format(12.6), "$13");
+    }
+}
@@ -0,0 +1,54 @@
+/// Arithmetic mean
+pub fn mean(values: &[f32]) -> f32 {
+    values.iter().sum::<f32>() / values.len() as f32
+}
+
+/// Median, averaging the two middle values for even sized samples
+pub fn median(values: &[f32]) -> f32 {
+    quantile(values, 0.5)
+}
+
+/// Mean after dropping `proportion` of the values from each end of the sorted sample
+pub fn trimmed_mean(values: &[f32], proportion: f32) -> f32 {
+    let sorted = sorted(values);
+    let trim = (sorted.len() as f32 * proportion).round() as usize;
+
+    if sorted.len() <= trim * 2 {
+        return median(values);
+    }
+
+    mean(&sorted[trim..sorted.len() - trim])
+}
+
+/// Quantile with linear interpolation between the closest ranks
+pub fn quantile(values: &[f32], q: f32) -> f32 {
+    let sorted = sorted(values);
+    let position = (sorted.len() - 1) as f32 * q;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f32)
+}
+
+fn sorted(values: &[f32]) -> Vec<f32> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median_of_odd_and_even_samples() {
+        assert_eq!(median(&[8.1, 9.4, 7.2, 10.0, 9.8]), 9.4);
+        assert_eq!(median(&[1.0, 4.0, 2.0, 3.0]), 2.5);
+    }
+
+    #[test]
+    fn test_trimmed_mean_drops_the_extremes() {
+        assert_eq!(trimmed_mean(&[8.1, 9.4, 7.2, 10.0, 9.8], 0.2), 9.099999);
+    }
+}
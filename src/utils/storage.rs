@@ -0,0 +1,101 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Directory of the files kept between runs: $DHANDHO_HOME, else $XDG_DATA_HOME/dhandho,
+/// else ~/.local/share/dhandho
+pub fn data_dir() -> PathBuf {
+    if let Ok(home) = env::var("DHANDHO_HOME") {
+        return PathBuf::from(home);
+    }
+
+    if let Ok(data_home) = env::var("XDG_DATA_HOME") {
+        return Path::new(&data_home).join("dhandho");
+    }
+
+    let home = env::var("HOME").expect("HOME is not set, set DHANDHO_HOME instead");
+
+    Path::new(&home)
+        .join(".local")
+        .join("share")
+        .join("dhandho")
+}
+
+/// User configuration file: $DHANDHO_CONFIG, else $XDG_CONFIG_HOME/dhandho/config.toml,
+/// else ~/.config/dhandho/config.toml
+pub fn config_path() -> PathBuf {
+    if let Ok(path) = env::var("DHANDHO_CONFIG") {
+        return PathBuf::from(path);
+    }
+
+    if let Ok(config_home) = env::var("XDG_CONFIG_HOME") {
+        return Path::new(&config_home).join("dhandho").join("config.toml");
+    }
+
+    let home = env::var("HOME").expect("HOME is not set, set DHANDHO_CONFIG instead");
+
+    Path::new(&home)
+        .join(".config")
+        .join("dhandho")
+        .join("config.toml")
+}
+
+/// Reads one JSON record per line, a missing file has no records
+pub fn read_lines<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(vec![]);
+    };
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|error| {
+                format!(
+                    "invalid record on line {} of {}: {}",
+                    index + 1,
+                    path.display(),
+                    error
+                )
+            })
+        })
+        .collect()
+}
+
+/// Appends a JSON record on its own line, creating the file when needed
+pub fn append_line<T: Serialize>(path: &Path, record: &T) {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).expect("cannot create the data directory");
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .expect("cannot open the data file");
+
+    writeln!(
+        file,
+        "{}",
+        serde_json::to_string(record).expect("cannot serialize the record")
+    )
+    .expect("cannot write the data file");
+}
+
+/// Replaces the file with one JSON record per line
+pub fn write_lines<T: Serialize>(path: &Path, records: &[T]) {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).expect("cannot create the data directory");
+    }
+
+    let content: String = records
+        .iter()
+        .map(|record| serde_json::to_string(record).expect("cannot serialize the record") + "\n")
+        .collect();
+
+    fs::write(path, content).expect("cannot write the data file");
+}
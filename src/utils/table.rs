@@ -0,0 +1,87 @@
+use comfy_table::{presets, ColumnConstraint, Table, Width};
+use serde::Deserialize;
+use std::str::FromStr;
+
+/// How reports with one row per item are written
+#[derive(PartialEq, Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    Table,
+    Csv,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(OutputFormat::Table),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!("unknown format `{}`, expected table or csv", s)),
+        }
+    }
+}
+
+/// Table with the borders only preset and fixed column widths
+pub fn new_table(header: Vec<&str>, widths: Vec<u16>) -> Table {
+    let mut table = Table::new();
+    table.load_preset(presets::UTF8_BORDERS_ONLY);
+    table.set_header(header);
+    table.set_constraints(
+        widths
+            .into_iter()
+            .map(|width| ColumnConstraint::Absolute(Width::Fixed(width))),
+    );
+
+    table
+}
+
+/// Renders the tables next to each other
+pub fn side_by_side(tables: &[&Table]) -> String {
+    let rendered: Vec<String> = tables.iter().map(|table| table.to_string()).collect();
+    let lines: Vec<Vec<&str>> = rendered
+        .iter()
+        .map(|table| table.lines().collect())
+        .collect();
+    let max_lines = lines.iter().map(|table| table.len()).max().unwrap_or(0);
+    // Each table is ~36 chars wide, wider ones are padded to their own width
+    let widths: Vec<usize> = lines
+        .iter()
+        .map(|table| {
+            table
+                .iter()
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap_or(0)
+                .max(36)
+        })
+        .collect();
+
+    let mut result = String::new();
+    for i in 0..max_lines {
+        let row: Vec<String> = lines
+            .iter()
+            .enumerate()
+            .map(|(index, table)| {
+                let line = table.get(i).unwrap_or(&"");
+                if index + 1 < lines.len() {
+                    format!("{:width$}", line, width = widths[index])
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect();
+
+        result.push_str(&row.join("  "));
+        result.push('\n');
+    }
+
+    result
+}
+
+/// Prints the tables side by side surrounded by blank lines
+pub fn print_side_by_side(tables: &[&Table]) {
+    println!();
+    print!("{}", side_by_side(tables));
+    println!();
+}